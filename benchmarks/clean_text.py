@@ -0,0 +1,40 @@
+# Run from the repository root with: python -m benchmarks.clean_text
+from timeit import timeit
+
+from utils import basic, verbalize
+
+guild_settings = {"read_emoji": True, "skip_spoilers": True, "code_blocks": "describe", "links": "domain", "repeated_chars": 3, "msg_length": 1000}
+
+samples = {
+    "short": "hello everyone",
+    "markdown": "**this** is *very* __important__ ~~not~~ ***really*** imo",
+    "emoji": "lol 😂😂😂 <:pepe_hands:123456789012345678> <a:party:123456789012345678> 👍",
+    "cjk": "見てhttps://example.com/page。すごい！||ネタバレ||です。",
+    "rtl": "\u202bمرحبا بالجميع، كيف حالكم؟\u202c شاهد www.example.com",
+    "links": "look at https://www.example.com/some/long/path?query=1 and www.discord.com rn",
+    "code": "try ```py\nprint('hello')\n``` or `print()`, ||spoiler|| iirc",
+    "spam": "looooooooooool " * 20 + "lol lol lol lol lol lol",
+    "numbers": "gg, the 3rd round starts at 14:30 with 1,250,000 points",
+    "wall": "this is a really long message " * 100,
+}
+
+stages = {
+    "remove_bidi_marks": basic.remove_bidi_marks,
+    "emojitoword": lambda text: basic.emojitoword(text, guild_settings["read_emoji"]),
+    "replace_acronyms": basic.replace_acronyms,
+    "replace_spoilers": basic.replace_spoilers,
+    "replace_code": basic.replace_code,
+    "remove_markdown": basic.remove_markdown,
+    "filter_links": basic.filter_links,
+    "collapse_repeats": lambda text: basic.collapse_repeats(text, guild_settings["repeated_chars"]),
+    "clean_text": lambda text: basic.clean_text(text, guild_settings),
+    "verbalize": lambda text: verbalize.verbalize(text, "en-us"),
+}
+
+if __name__ == "__main__":
+    runs = 1000
+    print(f"{'stage':<20}" + "".join(f"{sample:>12}" for sample in samples))
+
+    for stage_name, stage in stages.items():
+        timings = [timeit(lambda: stage(text), number=runs) / runs * 1_000_000 for text in samples.values()]
+        print(f"{stage_name:<20}" + "".join(f"{timing:>10.1f}us" for timing in timings))
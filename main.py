@@ -1,34 +1,73 @@
 import asyncio
+import base64
+import hmac
 import json
+import logging
 import re
 import shutil
 import time
+from collections import deque, namedtuple
 from asyncio.exceptions import TimeoutError as asyncio_TimeoutError
 from concurrent.futures._base import TimeoutError as concurrent_TimeoutError
-from configparser import ConfigParser
-from inspect import cleandoc
+from copy import deepcopy
+from datetime import datetime
+from difflib import SequenceMatcher, get_close_matches
+from hashlib import sha256
+from inspect import cleandoc, getclosurevars
 from io import BytesIO
-from os.path import exists
+from os.path import exists, getsize
+from string import Formatter
 from subprocess import call
 from sys import exc_info
 from traceback import format_exception
 from typing import Optional, Union
+from urllib.parse import urlencode
 
+import aiohttp
+from aiohttp import web
 import discord
 import gtts as gTTS
 from discord.ext import commands, tasks
-from mutagen.mp3 import MP3
 
 from patched_FFmpegPCM import FFmpegPCMAudio
-from utils import basic
+from utils import audio, backends, basic, bot_lists, doctor, logs, verbalize
+from utils.config import load_config, save_option
+from utils.i18n import get_text as _
+from utils.i18n import languages
+from utils.settings import analytics_class as analytics
+from utils.settings import saved_queue_class as saved_queue
 from utils.settings import blocked_users_class as blocked_users
+from utils.settings import opted_out_class as opted_out
+from utils.settings import default_settings
 from utils.settings import setlangs_class as setlangs
+from utils.settings import trials_class as trials
+from utils.settings import voices_class as voices
+from utils.settings import votes_class as votes
+from utils.settings import settings_audit_class as settings_audit
 from utils.settings import settings_class as settings
+from utils.settings import user_data
+from utils.settings import voice_channels_class as voice_channels
 
 #//////////////////////////////////////////////////////
-config = ConfigParser()
-config.read("config.ini")
-t = config["Main"]["Token"]
+config = load_config()
+t = config.get("Main", "token", fallback=None)
+elevenlabs_key = config.get("ElevenLabs", "api_key", fallback=None)
+
+# Warnings and errors from every logger go to the logs channel, -set_log changes the level per logger
+log_handler = logs.discord_handler(logging.DEBUG)
+logging.getLogger().addHandler(log_handler)
+logging.getLogger().setLevel(logging.WARNING)
+pipeline_logger = logging.getLogger("tts.pipeline")
+openai_key = config.get("OpenAI", "api_key", fallback=None)
+openai_model = config.get("OpenAI", "model", fallback="tts-1")
+sidecar_url = config.get("Sidecar", "url", fallback=None)
+character_limits = {**backends.character_limits, "sidecar": config.getint("Sidecar", "max_characters", fallback=0)}
+web_port = config.getint("Web", "port", fallback=0)
+bot_list_tokens = {bot_list: config.get("BotLists", f"{bot_list}_token", fallback=None) for bot_list in bot_lists.bot_lists}
+feedback_webhooks = {"suggestion": config.get("Feedback", "suggestions_webhook", fallback=None), "bug report": config.get("Feedback", "bugs_webhook", fallback=None)}
+oauth2_secret = config.get("OAuth2", "client_secret", fallback=None)
+oauth2_redirect = config.get("OAuth2", "redirect_uri", fallback=None)
+vote_secrets = {"topgg": config.get("Votes", "topgg_secret", fallback=None), "dbl": config.get("Votes", "dbl_secret", fallback=None)}
 
 # Define random variables
 BOT_PREFIX = "-"
@@ -37,6 +76,127 @@ settings_loaded = False
 before = time.monotonic()
 tts_langs = gTTS.lang.tts_langs(tld='co.uk')
 to_enabled = {True: "Enabled", False: "Disabled"}
+msg_length_caps = {True: 4000, False: 1000}
+audio_max_time_caps = {True: 300, False: 60}
+max_queue_length = 20
+max_prefixes = 5
+max_voice_aliases = 25
+max_role_voices = 25
+max_channel_voices = 25
+# What -set xsaid_format templates can say, such as "{user} in {channel} says"
+xsaid_placeholders = ("user", "channel", "attachments")
+max_xsaid_format = 50
+# With adaptive_speed, messages longer than this many characters are read faster, 10% per 100 more up to the cap
+adaptive_speed_start = 200
+adaptive_speed_cap = 1.5
+# In kbps, discord.py encodes at the default unless a premium server sets a bitrate, which is capped at the voice channel's
+default_bitrate = 128
+bitrate_range = (8, 384)
+setting_modes = {"links": ("skip", "domain", "full"), "code_blocks": ("skip", "describe", "full"), "overflow": ("drop_old", "drop_new", "merge"), "announce_events": ("off", "text", "voice"), "voice_mode": ("server", "random_per_user")}
+# Every server setting, in the order -settings shows them. -settings, -settings import, the panel and the dashboard all use this,
+# check(guild, value) returns what's wrong with a value of the right type, as a message formatted with the setting's name
+setting_info = namedtuple("setting_info", ("label", "unit", "premium", "check"), defaults=("", False, None))
+server_settings = {
+    "channel": setting_info("Channel", check=lambda guild, value: value and not isinstance(guild.get_channel(value), discord.TextChannel) and "`{setting}` is not a text or announcement channel in this server"),
+    "xsaid": setting_info("XSaid"),
+    "xsaid_on_change": setting_info("XSaid Only On Change"),
+    "xsaid_format": setting_info("XSaid Format", check=lambda guild, value: xsaid_format_error(value)),
+    "auto_join": setting_info("Auto Join"),
+    "follow": setting_info("Following", check=lambda guild, value: value and guild.get_member(value) is None and "`{setting}` is not a member of this server"),
+    "bot_ignore": setting_info("Ignore Bots"),
+    "announce_joins": setting_info("Announce Joins"),
+    "announce_events": setting_info("Announce Events"),
+    "now_playing": setting_info("Now Playing Message"),
+    "required_role": setting_info("Required Role", check=lambda guild, value: value and guild.get_role(value) is None and "`{setting}` is not a role in this server"),
+    "required_prefix": setting_info("Required Prefix", check=lambda guild, value: len(value) > 10 and "`{setting}` can be up to 10 characters"),
+    "pause_role": setting_info("Pause Role", check=lambda guild, value: value and guild.get_role(value) is None and "`{setting}` is not a role in this server"),
+    "allow_dm_tts": setting_info("Allow DM TTS"),
+    "read_attachments": setting_info("Read Attachments"),
+    "read_replies": setting_info("Read Replies"),
+    "links": setting_info("Links"),
+    "code_blocks": setting_info("Code Blocks"),
+    "read_emoji": setting_info("Read Emoji"),
+    "read_reactions": setting_info("Read Reactions"),
+    "skip_spoilers": setting_info("Skip Spoilers"),
+    "msg_length": setting_info("Max Message Length", check=lambda guild, value: not 1 <= value <= msg_length_caps[is_premium(guild)] and "`{setting}` has to be between 1 and {max_msg_length} characters"),
+    "repeated_chars": setting_info("Max Repeated Characters", check=lambda guild, value: value < 0 and "`{setting}` can't be negative"),
+    "skip_repeated": setting_info("Skip Repeated Messages"),
+    "audio_max_time": setting_info("Max Audio Time", unit="s", check=lambda guild, value: not 1 <= value <= audio_max_time_caps[is_premium(guild)] and "`{setting}` has to be between 1 and {max_audio_max_time} seconds"),
+    "volume": setting_info("Volume", unit="%", check=lambda guild, value: not 0 <= value <= 200 and "`{setting}` has to be between 0 and 200 percent"),
+    "normalize": setting_info("Normalize Loudness"),
+    "trim_silence": setting_info("Trim Silence"),
+    "adaptive_speed": setting_info("Adaptive Speed"),
+    "bitrate": setting_info("Bitrate", unit="kbps", premium=True, check=lambda guild, value: value and not bitrate_range[0] <= value <= bitrate_range[1] and "`{setting}` has to be between {min_bitrate} and {max_bitrate} kbps, or 0 for the default"),
+    "overflow": setting_info("Queue Overflow"),
+    "voice_mode": setting_info("Voice Mode"),
+    # Only checked once the voices have been fetched, -set elevenlabs_voice fetches them first
+    "elevenlabs_voice": setting_info("ElevenLabs Voice", premium=True, check=lambda guild, value: value and voices.get("elevenlabs") and value not in voices.get("elevenlabs") and "`{setting}` is not an ElevenLabs voice, do `-voices elevenlabs` to see them all"),
+    "openai_voice": setting_info("OpenAI Voice", premium=True, check=lambda guild, value: value and value not in backends.openai_voices and "`{setting}` is not an OpenAI voice, do `-voices openai` to see them all"),
+    "language": setting_info("Language", check=lambda guild, value: value not in languages and "`{setting}` has to be one of {languages}"),
+    "prefixes": setting_info("Prefixes", check=lambda guild, value: (
+        (not 1 <= len(value) <= max_prefixes and "`{setting}` needs between 1 and {max_prefixes} prefixes")
+        or next(filter(None, (prefix_error(prefix) if isinstance(prefix, str) else "`{setting}` contains an invalid prefix" for prefix in value)), False)
+    )),
+    "cooldowns": setting_info("Cooldowns", unit="s", check=lambda guild, value: not all(command in default_cooldowns and isinstance(seconds, int) and cooldown_minimums[is_premium(guild)] <= seconds <= 300 for command, seconds in value.items()) and "`{setting}` can only have {commands}, set to between {minimum} and 300 seconds"),
+    "voice_aliases": setting_info("Voice Aliases", check=lambda guild, value: (len(value) > max_voice_aliases or not all(isinstance(code, str) and code in tts_langs and re.match(r"^\w{1,20}$", alias) and alias not in tts_langs for alias, code in value.items())) and "`{setting}` can have up to {max_voice_aliases} names of up to 20 letters and numbers, which aren't voice codes themselves, each for a `-voices` code"),
+    "role_voices": setting_info("Role Voices", check=lambda guild, value: (len(value) > max_role_voices or not all(role_id.isdigit() and isinstance(code, str) and code in tts_langs for role_id, code in value.items())) and "`{setting}` can have up to {max_role_voices} role IDs, each for a `-voices` code"),
+    "channel_voices": setting_info("Channel Voices", check=lambda guild, value: (len(value) > max_channel_voices or not all(channel_id.isdigit() and isinstance(code, str) and code in tts_langs for channel_id, code in value.items())) and "`{setting}` can have up to {max_channel_voices} channel IDs, each for a `-voices` code"),
+    # Not shown in -settings, nicknames are shown per person and premium can't be changed by servers
+    "nicknames": setting_info(None, check=lambda guild, value: not all(isinstance(nickname, str) and re.match(r'^(\w|\s)+$', nickname) for nickname in value.values()) and "`{setting}` can only contain letters, numbers, and spaces"),
+    "premium": setting_info(None),
+}
+panel_settings = ("xsaid", "auto_join", "bot_ignore", "announce_joins", "read_attachments", "read_emoji", "skip_spoilers", "skip_repeated", "links", "code_blocks")
+# Commands for -help are put in these categories by name, the rest go in General, or Owner if only the owner or trusted people can use them
+help_categories = {
+    "Voice": ("join", "leave", "tts", "read", "pause", "resume", "checkperms", "voice", "voices"),
+    "Settings": ("setup", "channel", "settings", "set"),
+    "Premium": ("trial", "vote"),
+}
+# Converter errors that get their own message, before the usage of the command
+not_found_errors = {
+    commands.RoleNotFound: "Error: I couldn't find a role called `{argument}`, mention it or give its exact name or ID!",
+    commands.MemberNotFound: "Error: I couldn't find anyone called `{argument}`, mention them or give their exact name or ID!",
+    commands.ChannelNotFound: "Error: I couldn't find a channel called `{argument}`, mention it or give its exact name or ID!",
+}
+# Settings storing an ID, and how to mention it
+mention_settings = {"channel": "<#{}>", "follow": "<@{}>", "required_role": "<@&{}>", "pause_role": "<@&{}>"}
+# Custom checks, and who they let use the command
+check_descriptions = {"can_pause": "Administrator, or the `-set pause_role` role"}
+default_cooldowns = {"tts": 5, "read": 5}
+cooldown_minimums = {True: 0, False: 3}
+cooldown_mappings = dict()
+# Permission name, what it's needed for
+text_permissions = (
+    ("read_messages", "seeing the setup channel"),
+    ("send_messages", "replying to commands"),
+    ("read_message_history", "-read and the settings panel"),
+    ("embed_links", "-help, -settings, and -usage"),
+    ("attach_files", "-settings export and -mydata export"),
+    ("add_reactions", "the settings panel and -mydata delete"),
+    ("manage_messages", "pinning the now playing message"),
+)
+voice_permissions = (
+    ("view_channel", "seeing the voice channel"),
+    ("connect", "joining the voice channel"),
+    ("speak", "reading messages"),
+    ("use_voice_activation", "reading messages without push to talk"),
+)
+# Why -join can fail, and what to do about it
+join_failures = {
+    "joining": "Error: Already trying to join your voice channel!",
+    "wrong_channel": "Error: Wrong channel, do -channel get the channel that has been setup.",
+    "not_in_voice": "Error: You need to be in a voice channel to make me join your voice channel!",
+    "already_here": "Error: I am already in your voice channel!",
+    "in_other_channel": "Error: I am already in {channel}, join me there or do `-leave` first!",
+    "cant_view": "Error: I can't see your voice channel, give my role the View Channel permission in its settings!",
+    "cant_connect": "Error: I can't join your voice channel, give my role the Connect permission in its settings!",
+    "full": "Error: Your voice channel is full, raise its user limit or give my role the Move Members permission!",
+    "cant_speak": "Error: I can't speak in your voice channel, give my role the Speak permission in its settings!",
+    "cant_use_vad": "Error: I can't use voice activity in your voice channel, give my role the Use Voice Activity permission in its settings!",
+    "stage_cant_speak": "Error: I need to be a Stage Moderator, or have permission to request to speak, to talk in this Stage!",
+    "timed_out": "Error: Joining your voice channel timed out, Discord might be having problems, please try again later!",
+}
+number_emojis = ("1️⃣", "2️⃣", "3️⃣", "4️⃣", "5️⃣", "6️⃣", "7️⃣", "8️⃣", "9️⃣", "🔟")
 OPUS_LIBS = ('libopus-0.x86.dll', 'libopus-0.x64.dll', 'libopus-0.dll', 'libopus.so.0', 'libopus.0.dylib')
 
 intents = discord.Intents.none()
@@ -44,6 +204,9 @@ intents.voice_states = True
 intents.messages = True
 intents.guilds = True
 intents.members = True
+intents.reactions = True
+# discord.py 1.x doesn't know about scheduled events, so their intent is added by value and their events are read raw
+intents.value |= 1 << 16
 
 # Define useful functions
 def load_opus_lib(opus_libs=OPUS_LIBS):
@@ -79,29 +242,315 @@ async def chunk_guilds():
 
         bot.chunk_queue.remove(guild.id)
 
-# Define bot and remove overwritten commands
-bot = commands.AutoShardedBot(command_prefix=BOT_PREFIX, intents=intents, chunk_guilds_at_startup=False, case_insensitive=True)
+# Define bot
+def get_prefix(bot, message):
+    # Settings are already kept in memory, so this doesn't need its own cache
+    if message.guild is None:
+        return commands.when_mentioned_or(BOT_PREFIX)(bot, message)
+
+    return commands.when_mentioned_or(*settings.get(message.guild, "prefixes"))(bot, message)
+
+def guild_cooldown(ctx):
+    # Uses the server's -set cooldown, raising CommandOnCooldown for on_command_error like a normal cooldown
+    seconds = settings.get(ctx.guild, "cooldowns").get(ctx.command.name, default_cooldowns[ctx.command.name])
+    if not seconds:
+        return
+
+    mapping = cooldown_mappings.setdefault((ctx.command.name, seconds), commands.CooldownMapping.from_cooldown(1, seconds, commands.BucketType.member))
+    bucket = mapping.get_bucket(ctx.message)
+    retry_after = bucket.update_rate_limit()
+    if retry_after:
+        raise commands.CommandOnCooldown(bucket, retry_after)
+
+def invalid_voice(guild, text):
+    # Without slash command autocomplete, the next best thing is suggesting what they probably meant
+    names = {name.lower(): code for code, name in tts_langs.items()}
+    aliases = settings.get(guild, "voice_aliases") if guild else dict()
+    matches = get_close_matches(text.lower(), [*tts_langs, *aliases, *names], n=3, cutoff=0.6)
+    suggestions = list(dict.fromkeys(names.get(match, match) for match in matches))
+
+    if not suggestions:
+        return _(guild, "Invalid voice, do -voices")
+
+    return _(guild, "Invalid voice, did you mean {suggestions}? Do -voices to see them all", suggestions=" or ".join(f"`{suggestion}`" for suggestion in suggestions))
+
+def can_pause(ctx):
+    pause_role = settings.get(ctx.guild, "pause_role")
+    if ctx.author.guild_permissions.administrator or (pause_role and pause_role in [role.id for role in ctx.author.roles]):
+        return True
+
+    raise commands.MissingPermissions(["administrator"])
+
+def help_category(command):
+    command = command.root_parent or command
+    if any(check.__qualname__.startswith("is_owner.") or check.__qualname__.endswith("is_trusted") for check in command.checks):
+        return "Owner"
+
+    for category, names in help_categories.items():
+        if command.name in names:   return category
+
+    return "General"
+
+def check_permissions(command, check_name):
+    # has_permissions and bot_has_permissions keep the permissions they check in their closure
+    for check in command.checks:
+        if check.__qualname__.startswith(f"{check_name}."):
+            return [permission.replace("_", " ").title() for permission in getclosurevars(check).nonlocals["perms"]]
+
+    return list()
+
+def command_usage(command):
+    return f"-{command.qualified_name} {command.signature}".strip()
+
+def command_help(command):
+    # The first paragraph of a command's docstring is its description, and lines starting with - after it are examples
+    description, *rest = (command.help or "").split("\n\n")
+    examples = [line for paragraph in rest for line in paragraph.splitlines() if line.startswith("-")]
+
+    return description, examples
+
+def usage_help(guild, command):
+    # How the command is used, with its first example if it has one
+    examples = command_help(command)[1]
+    if examples:
+        return _(guild, "It's used like `{usage}`, for example `{example}`. Do `-help {command}` for more!", usage=command_usage(command), example=examples[0], command=command.qualified_name)
+
+    return _(guild, "It's used like `{usage}`, do `-help {command}` for more!", usage=command_usage(command), command=command.qualified_name)
+
+class BadDuration(commands.BadArgument):
+    def __init__(self, argument):
+        self.argument = argument
+        super().__init__(f"{argument} is not a duration")
+
+class Duration(commands.Converter):
+    # Converts durations such as 10m or 1h30m to seconds, a bare number is in default_unit so old usage still works
+    def __init__(self, default_unit="s"):
+        self.default_unit = default_unit
+
+    async def convert(self, ctx, argument):
+        seconds = basic.parse_duration(argument, self.default_unit)
+        if seconds is None:
+            raise BadDuration(argument)
+
+        return seconds
+
+def setting_changes(old_settings, new_settings):
+    # Nested settings (nicknames, aliases, cooldowns) are compared per key, instead of the whole dictionary
+    for setting, new_value in new_settings.items():
+        old_value = old_settings[setting]
+
+        if isinstance(new_value, dict):
+            for key in sorted({*old_value, *new_value}):
+                if old_value.get(key) != new_value.get(key):
+                    yield f"{setting}.{key}", old_value.get(key), new_value.get(key)
+
+        elif old_value != new_value:
+            yield setting, old_value, new_value
+
+def replacement_voices(old_voices, new_voices):
+    # Each removed ElevenLabs voice mapped to a voice with the same accent and gender, or "" to go back to gTTS
+    replacements = dict()
+    for voice_id, voice in old_voices.items():
+        if voice_id in new_voices:
+            continue
+
+        labels = [voice["labels"].get(label) for label in ("accent", "gender")]
+        matches = [new_id for new_id, new_voice in new_voices.items() if None not in labels and [new_voice["labels"].get(label) for label in ("accent", "gender")] == labels]
+        replacements[voice_id] = matches[0] if matches else ""
+
+    return replacements
+
+def speaking_speed(text):
+    return min(adaptive_speed_cap, 1 + max(0, len(text) - adaptive_speed_start) / 1000)
+
+def audio_options(guild, text=""):
+    # The ffmpeg options for playing one message, text is empty for announcements
+    # Engines are all different loudnesses, so normalising (EBU R128) keeps switching voices from being jarring
+    filters = [f"volume={settings.get(guild, 'volume') / 100}"]
+    if settings.get(guild, "normalize"):
+        filters.insert(0, "loudnorm=I=-16:TP=-1.5:LRA=11")
+    if settings.get(guild, "adaptive_speed") and speaking_speed(text) > 1:
+        filters.insert(0, f"atempo={speaking_speed(text):.2f}")
+
+    return f'-t {settings.get(guild, "audio_max_time")} -af "{",".join(filters)}" -loglevel "quiet"'
+
+def set_bitrate(vc, guild):
+    bitrate = settings.get(guild, "bitrate")
+    kbps = min(bitrate, vc.channel.bitrate // 1000) if bitrate and is_premium(guild) else default_bitrate
+
+    # discord.py only makes the encoder when something is first played
+    if vc.encoder is None:
+        vc.encoder = discord.opus.Encoder()
+    vc.encoder.set_bitrate(kbps)
+
+def describe_setting(guild, setting, value):
+    setting = setting.split(".")[0]
+    if isinstance(value, bool):
+        return _(guild, to_enabled[value])
+    if value in (None, "") or (setting in mention_settings and not value):
+        return _(guild, "has not been set yet")
+    if setting in mention_settings:
+        return mention_settings[setting].format(value)
+    if isinstance(value, list):
+        return " ".join(f"`{item}`" for item in value)
+
+    unit = server_settings[setting].unit
+    if isinstance(value, dict):
+        return ", ".join(f"`{key}: {item}{unit}`" for key, item in value.items()) or _(guild, "has not been set yet")
+
+    return f"`{value}{unit}`"
+
+def xsaid_format_error(template):
+    # Only bare placeholders are allowed, as format specs and attribute lookups could read out anything
+    error = "`{setting}` can be up to {max_xsaid_format} characters, with {placeholders} in curly brackets"
+    if len(template) > max_xsaid_format:
+        return error
+
+    try:    fields = [field for field in Formatter().parse(template) if field[1] is not None]
+    except ValueError:
+        return error
+
+    if any(name not in xsaid_placeholders or spec or conversion for _text, name, spec, conversion in fields):
+        return error
+
+def random_voice(user, voice_list):
+    # The same person always gets the same voice from the list, without it being stored anywhere
+    voice_list = sorted(voice_list)
+    if voice_list:
+        return voice_list[int(sha256(str(user.id).encode()).hexdigest(), 16) % len(voice_list)]
+
+def readable_text(message):
+    # The checks a server message has to pass to be read, on_message and edits share them
+    # Returns the text to read and if it started with -tts, or None if it shouldn't be read
+    guild = message.guild
+    saythis = message.clean_content.lower()
+
+    # Check if a setup channel
+    if message.channel.id != settings.get(guild, "channel"):
+        return None
+
+    # if author is a bot and bot ignore is on
+    if settings.get(guild, "bot_ignore") and message.author.bot:
+        return None
+
+    # if author has opted out of TTS, or author or server is blocked
+    if opted_out.check(message.author) or blocked_users.check(message.author) or blocked_users.check_guild(guild):
+        return None
+
+    # Check if author has the required role, webhooks have no roles
+    required_role = settings.get(guild, "required_role")
+    if required_role and required_role not in [role.id for role in getattr(message.author, "roles", list())]:
+        return None
+
+    # The message is lowercased, so the prefixes have to be too
+    prefixes = tuple(prefix.lower() for prefix in settings.get(guild, "prefixes"))
+    tts_prefix = next((prefix for prefix in prefixes if saythis.startswith(f"{prefix}tts")), None)
+    starts_with_tts = tts_prefix is not None
+    if starts_with_tts and tts_prefix != BOT_PREFIX:
+        saythis = saythis[len(tts_prefix) + 3:]
+
+    # Check for and remove the required prefix
+    required_prefix = settings.get(guild, "required_prefix")
+    if required_prefix:
+        if not saythis.startswith(required_prefix.lower()):
+            return None
+
+        saythis = saythis[len(required_prefix):]
+
+    # If message is empty and there is no attachment/embed
+    if len(saythis) == 0 and not message.attachments and not message.embeds:
+        return None
+
+    # Ignore messages starting with the prefix that are probably commands (also advertised as a feature when it is wrong lol)
+    if saythis.startswith(prefixes) and not starts_with_tts:
+        return None
+
+    return saythis, starts_with_tts
+
+def member_voice(guild, member, channel=None):
+    # Someone's own -voice, then the voice of their highest role that has one, then the channel's voice,
+    # then a random one if the server wants, then the default
+    if guild is None or setlangs.export_user(member):
+        return setlangs.get(member)
+
+    role_voices = settings.get(guild, "role_voices")
+    for role in sorted(getattr(member, "roles", ()), key=lambda role: role.position, reverse=True):
+        if str(role.id) in role_voices:
+            return role_voices[str(role.id)]
+
+    channel_voices = settings.get(guild, "channel_voices")
+    if channel is not None and str(channel.id) in channel_voices:
+        return channel_voices[str(channel.id)]
+
+    if settings.get(guild, "voice_mode") == "random_per_user":
+        # Only accents of the default language, so messages are still read in the right language
+        language = setlangs.get(member).split("-")[0]
+        return random_voice(member, [code for code in tts_langs if code.split("-")[0] == language]) or setlangs.get(member)
+
+    return setlangs.get(member)
+
+def prefix_error(prefix):
+    if not 1 <= len(prefix) <= 5:
+        return "The prefix has to be between 1 and 5 characters long!"
+    if any(char.isspace() or char in "*_~|`>" for char in prefix):
+        return "The prefix can't contain spaces or markdown!"
+    if any(char in "@#<:" for char in prefix):
+        return "The prefix can't contain anything that could be a mention or emote!"
+
+bot = commands.AutoShardedBot(command_prefix=get_prefix, intents=intents, chunk_guilds_at_startup=False, case_insensitive=True)
 bot.chunk_queue = list()
 
-if exists("cogs/common_user.py"):
-    bot.load_extension("cogs.common_owner")
-    bot.load_extension("cogs.common_trusted")
-    bot.load_extension("cogs.common_user")
-elif exists("cogs/common.py"):
-    bot.load_extension("cogs.common")
-else:
-    print("Error: Cannot find cogs to load? Did you do 'git clone --recurse-submodules'?")
-    raise SystemExit
-
-for overwriten_command in ("help", "end", "botstats"):
-    bot.remove_command(overwriten_command)
+# Dashboard WebSockets watching each server, guild_id: set of websockets
+dashboard_feeds = dict()
+
+def dashboard_event(guild, event, **data):
+    # Sent to every dashboard watching this server, without waiting for them
+    for websocket in dashboard_feeds.get(guild.id, set()).copy():
+        if websocket.closed:    dashboard_feeds[guild.id].discard(websocket)
+        else:   asyncio.ensure_future(websocket.send_json({"event": event, **data}))
+
+def is_premium(guild):
+    return settings.get(guild, "premium") or trials.active(guild)
+
+@bot.check
+def not_blocked(ctx):
+    # Blocked people and servers can't use any commands
+    return not blocked_users.check(ctx.author) and not (ctx.guild and blocked_users.check_guild(ctx.guild))
+
+def load_cogs():
+    # Load the cogs submodule and remove the commands overwritten here
+    if exists("cogs/common_user.py"):
+        bot.load_extension("cogs.common_owner")
+        bot.load_extension("cogs.common_trusted")
+        bot.load_extension("cogs.common_user")
+    elif exists("cogs/common.py"):
+        bot.load_extension("cogs.common")
+    else:
+        print("Error: Cannot find cogs to load? Did you do 'git clone --recurse-submodules'?")
+        raise SystemExit
+
+    for overwriten_command in ("help", "end", "botstats", "suggest"):
+        bot.remove_command(overwriten_command)
+
+    bot.add_cog(Main(bot))
+    bot.add_cog(Settings(bot))
 #//////////////////////////////////////////////////////
 class Main(commands.Cog):
     def __init__(self, bot):
         self.bot = bot
+        self.recent_feedback = deque(maxlen=100)
+        self.now_playing_text = dict()
+        self.now_playing_messages = dict()
+        self.started_events = set()
+        self.pending_reactions = dict()
+        self.reading = dict()
 
     def cog_unload(self):
         self.avoid_file_crashes.cancel()
+        self.send_logs.cancel()
+        self.trial_reminders.cancel()
+        self.post_stats.cancel()
+        self.refresh_voice_lists.cancel()
 
     def is_trusted(ctx):
         if str(ctx.author.id) in bot.trusted: return True
@@ -113,6 +562,14 @@ class Main(commands.Cog):
             settings.save()
             setlangs.save()
             blocked_users.save()
+            settings_audit.save()
+            analytics.save()
+            saved_queue.save()
+            voice_channels.save()
+            opted_out.save()
+            trials.save()
+            votes.save()
+            voices.save()
         except Exception as e:
             error = getattr(e, 'original', e)
 
@@ -123,24 +580,502 @@ class Main(commands.Cog):
             else:
                 await self.bot.channels["errors"].send(temp)
 
+    @tasks.loop(hours=1.0)
+    async def trial_reminders(self):
+        for guild_id in trials.expired():
+            guild = self.bot.get_guild(guild_id)
+            channel = guild and guild.get_channel(settings.get(guild, "channel"))
+            if channel is None:
+                continue
+
+            try:    await channel.send(_(guild, "Your premium trial has ended! Premium voices and higher limits are no longer available, join https://discord.gg/zWPWwQC to find out how to keep them."))
+            except discord.errors.HTTPException:    pass
+
+    @tasks.loop(seconds=10.0)
+    async def send_logs(self):
+        # At most 5 messages every 10 seconds, anything more waits for the next loop
+        text = log_handler.take()
+        for _chunk in range(5):
+            if not text: break
+
+            try:    await self.bot.channels["logs"].send(f"```{text[:1900]}```")
+            except discord.errors.HTTPException:    pass
+            text = text[1900:]
+
+        if text:    log_handler.records.insert(0, text)
+
+    @tasks.loop(hours=24.0)
+    async def refresh_voice_lists(self):
+        for problem in await self.update_voices():
+            await self.bot.channels["errors"].send(problem)
+
+    async def update_voices(self):
+        # Keeps the last working list if the backend can't be reached, returns what went wrong
+        problems = list()
+        if elevenlabs_key:
+            old_voices = voices.get("elevenlabs")
+            try:    voices.set("elevenlabs", await backends.elevenlabs_voices(elevenlabs_key))
+            except aiohttp.ClientError as error:
+                problems.append(f"Couldn't refresh the ElevenLabs voices, the last list fetched is still used: {error}")
+            else:
+                await self.replace_removed_voices("elevenlabs_voice", voices.get("elevenlabs"), replacement_voices(old_voices, voices.get("elevenlabs")))
+
+        await self.replace_removed_voices("openai_voice", backends.openai_voices, dict())
+        return problems
+
+    async def replace_removed_voices(self, setting, available, replacements):
+        # Servers using a voice the backend no longer has are moved to its replacement, told why, and the change is kept in -settings history
+        replaced = 0
+        for guild in self.bot.guilds:
+            voice = settings.get(guild, setting)
+            if not voice or voice in available:
+                continue
+
+            old_settings = settings.get_all(guild)
+            replacement = replacements.get(voice, "")
+            settings.set(guild, setting, replacement)
+            self.bot.get_cog("Settings").record_changes(guild, guild.me, old_settings)
+            replaced += 1
+
+            channel = guild.get_channel(settings.get(guild, "channel"))
+            if channel is None:
+                continue
+
+            if replacement:
+                text = _(guild, "The voice `{voice}` has been removed, so messages are now read with `{replacement}`. Do `-set {setting}` to choose another.", voice=voice, replacement=replacement, setting=setting)
+            else:
+                text = _(guild, "The voice `{voice}` has been removed, so messages are now read with gTTS. Do `-set {setting}` to choose another.", voice=voice, setting=setting)
+
+            try:    await channel.send(text)
+            except discord.errors.HTTPException:    pass
+
+        if replaced:
+            await self.bot.channels["logs"].send(f"Replaced the removed `{setting}` of {replaced} servers")
+
+    @tasks.loop(minutes=30.0)
+    async def post_stats(self):
+        for bot_list, token in bot_list_tokens.items():
+            if not token:
+                continue
+
+            # A list being down shouldn't stop the others being posted to
+            try:    await bot_lists.post_stats(bot_list, token, self.bot.user.id, len(self.bot.guilds), self.bot.shard_count or 1)
+            except (aiohttp.ClientError, asyncio_TimeoutError):    await self.bot.on_error(f"post_stats to {bot_list}")
+
+    @post_stats.before_loop
+    @trial_reminders.before_loop
+    @send_logs.before_loop
     @avoid_file_crashes.before_loop
     async def before_file_saving_loop(self):
         await self.bot.wait_until_ready()
 
+    def make_tts(self, text, lang):
+        temp_store_for_mp3 = BytesIO()
+        gTTS.gTTS(text=text, lang=lang).write_to_fp(temp_store_for_mp3)
+        temp_store_for_mp3.seek(0)
+
+        return temp_store_for_mp3
+
+    async def synthesise(self, guild, text, lang, author=None):
+        chain = list()
+
+        # Paid voices are premium only, and replace gTTS for the whole server, or just for voters' messages
+        if is_premium(guild) or (author and votes.active(author)):
+            random_per_user = author is not None and settings.get(guild, "voice_mode") == "random_per_user"
+
+            voice_id = settings.get(guild, "elevenlabs_voice")
+            if random_per_user and voice_id:
+                voice_id = random_voice(author, voices.get("elevenlabs")) or voice_id
+            if elevenlabs_key and voice_id:
+                chain.append(("elevenlabs", lambda chunk: backends.elevenlabs(elevenlabs_key, voice_id, chunk)))
+
+            openai_voice = settings.get(guild, "openai_voice")
+            if random_per_user and openai_voice:
+                openai_voice = random_voice(author, backends.openai_voices)
+            if openai_key and openai_voice:
+                chain.append(("openai", lambda chunk: backends.openai(openai_key, openai_model, openai_voice, chunk)))
+
+        # Self hosters can replace gTTS with their own engine
+        if sidecar_url:
+            chain.append(("sidecar", lambda chunk: backends.sidecar(sidecar_url, chunk, lang, config.getint("Sidecar", "timeout", fallback=10), config.getint("Sidecar", "retries", fallback=2))))
+
+        chain.append(("gtts", lambda chunk: self.bot.loop.run_in_executor(None, self.make_tts, chunk, lang)))
+
+        # If a backend fails, fall back to the next one instead of dropping the message
+        for backend_name, backend in chain:
+            breaker = self.bot.breakers.get(backend_name)
+            if breaker and breaker.is_open():
+                analytics.add(f"{backend_name}_fallbacks")
+                continue
+
+            # Too long for one request, so the sentences are synthesised at the same time and their mp3s joined
+            chunks = basic.split_sentences(text, character_limits[backend_name]) if character_limits.get(backend_name) else [text]
+            try:    temp_store_for_mp3 = BytesIO(b"".join(part.getvalue() for part in await asyncio.gather(*(backend(chunk) for chunk in chunks))))
+            except (gTTS.tts.gTTSError, ValueError, aiohttp.ClientError, asyncio_TimeoutError):
+                if backend_name == "gtts":  raise
+
+                analytics.add(f"{backend_name}_fallbacks")
+                if breaker.failure():
+                    await self.bot.channels["errors"].send(f"The {backend_name} TTS backend failed {breaker.failures} times in a row, skipping it for {breaker.cooldown} seconds.")
+                continue
+
+            if breaker:  breaker.success()
+
+            if backend_name in ("elevenlabs", "openai"):
+                analytics.add(f"{backend_name}_characters", len(text))
+
+            return temp_store_for_mp3
+
+    def is_paused(self, guild):
+        until = self.bot.paused.get(guild.id, 0)
+        return until is None or until > time.monotonic()
+
+    async def announce(self, guild, text, lang):
+        if self.is_paused(guild):
+            return
+
+        try:    temp_store_for_mp3 = await self.synthesise(guild, text, lang)
+        except (AssertionError, gTTS.tts.gTTSError, ValueError, aiohttp.ClientError, asyncio_TimeoutError):    return
+
+        self.bot.queue.setdefault(guild.id, dict())
+        self.bot.announcements.setdefault(guild.id, list()).append(temp_store_for_mp3)
+        await self.play_queue(guild)
+
+    async def read_message(self, message, guild, saythis, starts_with_tts=False, edited=False):
+        if opted_out.check(message.author) or blocked_users.check(message.author) or blocked_users.check_guild(guild) or self.is_paused(guild):
+            return
+
+        started = time.monotonic()
+
+        # Sometimes bot.guilds is wrong, because intents
+        if guild.id not in self.bot.queue:
+            self.bot.queue[guild.id] = dict()
+
+        # Skip messages that are almost the same as the author's last message
+        last_message = basic.get_value(self.bot.last_message, guild.id, message.author.id)
+        self.bot.last_message.setdefault(guild.id, dict())[message.author.id] = saythis
+
+        if not edited and settings.get(guild, "skip_repeated") and last_message and SequenceMatcher(None, last_message, saythis).ratio() > 0.8:
+            return
+
+        # Emojis, acronyms, spoilers, code blocks, markdown, links, repeats, and length
+        saythis = basic.clean_text(saythis, settings.get_all(guild), starts_with_tts)
+        # Then abbreviations, times, ordinals and numbers as words, in the language they will be read in
+        saythis = verbalize.verbalize(saythis, member_voice(guild, message.author, message.channel))
+
+        if settings.get(guild, "read_replies") and message.reference is not None:
+            replied_to = await self.replied_to(message)
+            if replied_to is not None:
+                saythis = f"replying to {settings.nickname.get(guild, replied_to)}: {saythis}"
+
+        # Toggleable X said and attachment detection
+        xsaid = settings.get(guild, "xsaid")
+        if xsaid and settings.get(guild, "xsaid_on_change"):
+            xsaid = basic.get_value(self.bot.last_author, guild.id) != message.author.id
+
+        read_attachments = settings.get(guild, "read_attachments")
+        if read_attachments:
+            format = " and ".join(filter(None, (basic.describe_attachments(message.attachments), basic.describe_embeds(message.embeds))))
+        else:
+            format = basic.exts_to_format(message.attachments)
+
+        xsaid_format = settings.get(guild, "xsaid_format")
+        if xsaid and xsaid_format:
+            said_name = settings.nickname.get(guild, message.author)
+            saythis = f"{xsaid_format.format(user=said_name, channel=getattr(message.channel, 'name', 'DMs'), attachments=len(message.attachments))} {saythis}"
+            if format and read_attachments:
+                saythis = f"{saythis}. sent {format}."
+
+        elif xsaid:
+            said_name = settings.nickname.get(guild, message.author)
+
+            if format:
+                if len(saythis) == 0:
+                    saythis = f"{said_name} sent {format}."
+                else:
+                    saythis = f"{said_name} sent {format} and said {saythis}"
+            else:
+                saythis = f"{said_name} said: {saythis}"
+
+        elif format and read_attachments:
+            if len(saythis) == 0:
+                saythis = f"sent {format}."
+            else:
+                saythis = f"{saythis}. sent {format}."
+
+        if basic.remove_chars(saythis, " ", "?", ".", ")", "'", '"') == "":
+            return
+
+        cleaned = time.monotonic()
+
+        # Queue cap, merge only works if the newest message in the queue is from the same person
+        queue = self.bot.queue[guild.id]
+        overflow = settings.get(guild, "overflow") if len(queue) >= max_queue_length else None
+        merge_into = max(queue) if overflow == "merge" and self.bot.last_author.get(guild.id) == message.author.id and not isinstance(queue[max(queue)], asyncio.Future) else None
+        if overflow == "drop_new" or (overflow == "merge" and merge_into is None):
+            return analytics.add("dropped")
+
+        # Rate limits, so one server can't hold up gTTS for everyone else
+        guild_limit = self.bot.rate_limits.setdefault(guild.id, basic.token_bucket(config.getint("Limits", "guild_messages_per_minute", fallback=30)))
+        if not guild_limit.take() or not self.bot.rate_limits["global"].take():
+            return analytics.add("rate_limited")
+
+        self.bot.last_author[guild.id] = message.author.id
+
+        # Read language file
+        lang = member_voice(guild, message.author, message.channel)
+
+        # Hold the message's place in the queue while it's synthesised, so later messages can't be read before it
+        # play_queue waits on the placeholder, which is done once the audio is in the queue or the message is dropped
+        placeholder = None
+        if not merge_into:
+            # The message being read stays in the queue until it finishes, so it can't be dropped
+            dropped = min((message_id for message_id in queue if message_id != self.reading.get(guild.id)), default=None) if overflow == "drop_old" else None
+            if dropped is not None:
+                queue.pop(dropped)
+                saved_queue.remove(guild, dropped)
+
+            placeholder = queue[message.id] = asyncio.get_event_loop().create_future()
+
+        try:
+            try:  temp_store_for_mp3 = await self.synthesise(guild, saythis, lang, message.author)
+            except AssertionError:
+                return
+            except (gTTS.tts.gTTSError, ValueError, aiohttp.ClientError, asyncio_TimeoutError):
+                return await message.channel.send(_(guild, "Ah! gTTS couldn't process {link} for some reason, please try again later.", link=message.jump_url))
+
+            # The queue may have been played or cleared while synthesising
+            queue = self.bot.queue.get(guild.id, dict())
+            if merge_into in queue:
+                queue[merge_into] = BytesIO(queue[merge_into].getvalue() + temp_store_for_mp3.getvalue())
+                saved_queue.merge(guild, merge_into, saythis)
+            elif merge_into or message.id in queue:
+                queue[message.id] = temp_store_for_mp3
+                saved_queue.add(guild, message, saythis, lang)
+            else:
+                return
+            del temp_store_for_mp3
+        finally:
+            # Failed, cancelled or not, the placeholder never stays in the queue
+            if placeholder is not None:
+                queue = self.bot.queue.get(guild.id, dict())
+                if queue.get(message.id) is placeholder:
+                    queue.pop(message.id)
+                placeholder.set_result(None)
+
+        dashboard_event(guild, "queue", length=len(queue))
+
+        analytics.add("messages")
+        analytics.add("characters", len(saythis))
+        analytics.add_guild(guild, message.author, len(saythis))
+
+        # -set_log tts.pipeline DEBUG to see where the time goes
+        pipeline_logger.debug("%s in %s: cleaned in %.0fms, synthesised in %.0fms, %s queued", message.id, guild.id, (cleaned - started) * 1000, (time.monotonic() - cleaned) * 1000, len(queue))
+
+        await self.play_queue(guild)
+
+    async def replay_queue(self, guild):
+        # Read messages that were still queued when TTS Bot last stopped, in their original order
+        saved_queue.expire(config.getint("Limits", "queue_replay_age", fallback=300))
+        queue = self.bot.queue.setdefault(guild.id, dict())
+        entries = [(message_id, entry) for message_id, entry in saved_queue.get(guild) if message_id not in queue]
+        if not entries:
+            return
+
+        placeholders = {message_id: asyncio.get_event_loop().create_future() for message_id, entry in entries}
+        queue.update(placeholders)
+
+        try:
+            for message_id, entry in entries:
+                try:    temp_store_for_mp3 = await self.synthesise(guild, entry["text"], entry["lang"], discord.Object(id=entry["author"]))
+                except (AssertionError, gTTS.tts.gTTSError, ValueError, aiohttp.ClientError, asyncio_TimeoutError):
+                    temp_store_for_mp3 = None
+
+                queue = self.bot.queue.get(guild.id, dict())
+                if queue.get(message_id) is not placeholders[message_id]:
+                    pass
+                elif temp_store_for_mp3 is None:
+                    queue.pop(message_id)
+                    saved_queue.remove(guild, message_id)
+                else:
+                    queue[message_id] = temp_store_for_mp3
+
+                placeholders[message_id].set_result(None)
+        finally:
+            # Any left were never synthesised, because this was cancelled
+            queue = self.bot.queue.get(guild.id, dict())
+            for message_id, placeholder in placeholders.items():
+                if placeholder.done():
+                    continue
+
+                if queue.get(message_id) is placeholder:
+                    queue.pop(message_id)
+                placeholder.set_result(None)
+
+        await self.play_queue(guild)
+
+    async def play_queue(self, guild):
+        # Queue, please don't touch this, it works somehow
+        while self.bot.playing[guild.id] != 0:
+            if self.bot.playing[guild.id] == 2: return
+            await asyncio.sleep(0.5)
+
+        self.bot.playing[guild.id] = 1
+        announcements = self.bot.announcements.setdefault(guild.id, list())
+        trim_silence = settings.get(guild, "trim_silence")
+
+        while self.bot.queue[guild.id] != dict() or announcements != list():
+            # Announcements are low priority, so only read them once every message has been
+            if self.bot.queue[guild.id] != dict():
+                # Sort Queue
+                self.bot.queue[guild.id] = basic.sort_dict(self.bot.queue[guild.id])
+
+                # Select first in queue
+                message_id_to_read = next(iter(self.bot.queue[guild.id]))
+                selected = self.bot.queue[guild.id][message_id_to_read]
+
+                # Still being synthesised, wait for it instead of skipping ahead
+                if isinstance(selected, asyncio.Future):
+                    await selected
+                    continue
+            else:
+                message_id_to_read = None
+                selected = announcements[0]
+
+            selected.seek(0)
+
+            entry = dict(saved_queue.get(guild)).get(message_id_to_read, dict())
+            author = entry.get("author")
+            options = audio_options(guild, entry.get("text", ""))
+            dashboard_event(guild, "speaking", message_id=message_id_to_read, author=author)
+            self.update_now_playing(guild, author)
+
+            if message_id_to_read is not None:
+                pipeline_logger.debug("%s in %s: started playing %.0fms after being sent", message_id_to_read, guild.id, (datetime.utcnow() - discord.utils.snowflake_time(message_id_to_read)).total_seconds() * 1000)
+
+            # Play selected audio, retrying once if playback fails part way
+            vc = guild.voice_client
+            if vc is not None:
+                set_bitrate(vc, guild)
+                self.reading[guild.id] = message_id_to_read
+                for attempt in range(2):
+                    selected.seek(0)
+                    error = await self.play_audio(vc, selected.read(), options, trim_silence)
+                    if error is None:
+                        break
+
+                    analytics.add("playback_retries" if attempt == 0 else "playback_failures")
+                else:
+                    # The message text isn't sent, only where it failed
+                    await self.bot.channels["errors"].send(f"Playing message {message_id_to_read} in {guild.id} failed twice:\n```{type(error).__name__}: {error}```")
+
+                # Delete said message from queue
+                self.reading.pop(guild.id, None)
+                if message_id_to_read in self.bot.queue[guild.id]:
+                    del self.bot.queue[guild.id][message_id_to_read]
+                    saved_queue.remove(guild, message_id_to_read)
+                    dashboard_event(guild, "queue", length=len(self.bot.queue[guild.id]))
+                elif selected in announcements:
+                    announcements.remove(selected)
+
+            else:
+                # If not in a voice channel anymore, clear the queue
+                self.bot.queue[guild.id] = dict()
+                saved_queue.clear(guild)
+                announcements.clear()
+                dashboard_event(guild, "queue", length=0)
+
+        # Queue should be empty now, let next on_message though
+        self.bot.playing[guild.id] = 0
+        dashboard_event(guild, "speaking", message_id=None, author=None)
+        self.update_now_playing(guild, None)
+
+    async def play_audio(self, vc, mp3, options, trim_silence=False):
+        # Returns the error that stopped playback, or None if it finished
+        errors = list()
+        source = FFmpegPCMAudio(mp3, pipe=True, options=options)
+        if trim_silence:
+            source = audio.silence_trimmer(source)
+
+        try:    vc.play(source, after=errors.append)
+        except discord.errors.ClientException:  return # sliences desyncs between discord.py and discord, implement actual fix soon!
+
+        while vc.is_playing() or not errors:
+            if not vc.is_connected():   return
+            await asyncio.sleep(0.5)
+
+        return errors[0]
+
+    def update_now_playing(self, guild, author_id):
+        if not settings.get(guild, "now_playing"):
+            return
+
+        if author_id is None:
+            text = _(guild, "🔇 Not reading anything")
+        else:
+            member = guild.get_member(author_id)
+            text = _(guild, "🔊 Reading: {name}", name=settings.nickname.get(guild, member) if member else _(guild, "someone"))
+
+        # Only one task edits each message, so edits stay spaced out however fast people talk
+        running = guild.id in self.now_playing_text
+        self.now_playing_text[guild.id] = text
+        if not running:
+            self.bot.loop.create_task(self.edit_now_playing(guild))
+
+    async def edit_now_playing(self, guild):
+        channel = guild.get_channel(settings.get(guild, "channel"))
+        message = self.now_playing_messages.get(guild.id)
+
+        try:
+            if channel is not None and message is None:
+                message = next((pin for pin in await channel.pins() if pin.author == guild.me and pin.content.startswith(("🔊", "🔇"))), None)
+
+            while channel is not None:
+                text = self.now_playing_text[guild.id]
+                if message is None or message.channel != channel:
+                    message = await channel.send(text)
+                    try:    await message.pin()
+                    except discord.errors.Forbidden:    pass
+                elif message.content != text:
+                    await message.edit(content=text)
+
+                await asyncio.sleep(5)
+                if self.now_playing_text[guild.id] == text:
+                    break
+        except discord.errors.HTTPException:
+            message = None
+
+        self.now_playing_text.pop(guild.id, None)
+        self.now_playing_messages[guild.id] = message
 #//////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
     @commands.command()
     @commands.is_owner()
     async def end(self, ctx):
+        """Saves every file and shuts TTS Bot down"""
         self.avoid_file_crashes.cancel()
+        self.send_logs.cancel()
+        self.trial_reminders.cancel()
+        self.post_stats.cancel()
+        self.refresh_voice_lists.cancel()
         settings.save()
         setlangs.save()
         blocked_users.save()
-
+        settings_audit.save()
+        analytics.save()
+        saved_queue.save()
+        voice_channels.save()
+        opted_out.save()
+        trials.save()
+        votes.save()
+        voices.save()
+
+        if self.bot.web_runner:   await self.bot.web_runner.cleanup()
         await self.bot.close()
 
     @commands.command()
     @commands.is_owner()
     async def leave_unused_guilds(self, ctx, sure: bool = False):
+        """Leaves every server that hasn't setup TTS Bot, `true` to actually leave"""
         guilds_to_leave = []
         with open("settings.json") as f:
             temp_settings = f.read()
@@ -163,6 +1098,7 @@ class Main(commands.Cog):
     @commands.command()
     @commands.is_owner()
     async def channellist(self, ctx):
+        """Lists every voice channel TTS Bot is in"""
         channellist = str()
         for guild1 in self.bot.guilds:
             try:  channellist = f"{channellist} \n{str(guild1.voice_client.channel)} in {guild1.name}"
@@ -177,6 +1113,11 @@ class Main(commands.Cog):
     @commands.command()
     @commands.is_owner()
     async def trust(self, ctx, mode, user: Union[discord.User, str] = ""):
+        """Lists, adds or removes trusted people
+
+        -trust add @person
+        -trust list
+        """
         if mode == "list":
             await ctx.send("\n".join(self.bot.trusted))
 
@@ -186,8 +1127,7 @@ class Main(commands.Cog):
         elif mode == "add":
             self.bot.trusted.append(str(user.id))
             config["Main"]["trusted_ids"] = str(self.bot.trusted)
-            with open("config.ini", "w") as configfile:
-                config.write(configfile)
+            save_option("Main", "trusted_ids", str(self.bot.trusted))
 
             await ctx.send(f"Added {str(user)} | {user.id} to the trusted members")
 
@@ -195,22 +1135,82 @@ class Main(commands.Cog):
             if str(user.id) in self.bot.trusted:
                 self.bot.trusted.remove(str(user.id))
                 config["Main"]["trusted_ids"] = str(self.bot.trusted)
-                with open("config.ini", "w") as configfile:
-                    config.write(configfile)
+                save_option("Main", "trusted_ids", str(self.bot.trusted))
 
                 await ctx.send(f"Removed {str(user)} | {user.id} from the trusted members")
 
+    @commands.command()
+    @commands.check(is_trusted)
+    async def premium(self, ctx, guild_id: int, value: bool, duration: Duration("d") = None):
+        """Gives or takes away premium for a server, with a duration it's given as a trial instead
+
+        -premium 123456789012345678 true
+        -premium 123456789012345678 true 30d
+        """
+        if value and duration is not None:
+            trials.start(discord.Object(id=guild_id), duration)
+            return await ctx.send(f"Gave {guild_id} a premium trial for {basic.describe_duration(duration)}")
+
+        settings.set(discord.Object(id=guild_id), "premium", value)
+        await ctx.send(f"Premium is now {to_enabled[value]} for {guild_id}")
+
+    @commands.command()
+    @commands.is_owner()
+    async def set_log(self, ctx, target: str, level: str):
+        """Changes the log level of a logger, `root` for every logger
+
+        -set_log root DEBUG
+        """
+        level = level.upper()
+        if level not in ("DEBUG", "INFO", "WARNING", "ERROR", "CRITICAL", "NOTSET"):
+            return await ctx.send("Error: Level has to be one of DEBUG, INFO, WARNING, ERROR, CRITICAL, or NOTSET")
+
+        # NOTSET goes back to using the parent logger's level
+        logger = logging.getLogger(None if target == "root" else target)
+        logger.setLevel(level)
+
+        await ctx.send(f"Set the log level of {logger.name} to {level}")
+
+    @commands.command(aliases=["refreshvoices"])
+    @commands.is_owner()
+    async def refresh_voices(self, ctx):
+        """Fetches every backend's voice list now, instead of waiting for the daily refresh"""
+        problems = await self.update_voices()
+        await ctx.send("\n".join(problems) or f"Refreshed the voice lists! ElevenLabs has {len(voices.get('elevenlabs'))} voices.")
+
+    @commands.command(aliases=["ttsdoctor"])
+    @commands.is_owner()
+    async def doctor(self, ctx):
+        """Checks config.ini, ffmpeg and opus, and every configured API key and webhook"""
+        async with ctx.typing():
+            problems = doctor.check_config(config) + doctor.check_environment() + await doctor.check_services(config)
+
+        if not problems:
+            return await ctx.send("✅ Everything looks fine!")
+
+        await ctx.send("\n".join(f"❌ {problem}" for problem in problems))
+
     @commands.command()
     @commands.check(is_trusted)
     async def save_files(self, ctx):
+        """Saves every file now, instead of waiting for the next save"""
         settings.save()
         setlangs.save()
         blocked_users.save()
+        settings_audit.save()
+        analytics.save()
+        saved_queue.save()
+        voice_channels.save()
+        opted_out.save()
+        trials.save()
+        votes.save()
+        voices.save()
         await ctx.send("Saved all files!")
 
     @commands.command()
     @commands.check(is_trusted)
     async def cleanup(self, ctx):
+        """Removes settings for servers and people TTS Bot can't see anymore"""
         guild_id_list = [str(guild.id) for guild in self.bot.guilds]
 
         user_id_list = list()
@@ -224,21 +1224,49 @@ class Main(commands.Cog):
 
         await ctx.send("Done!")
 
-    @commands.command()
+    @commands.group(invoke_without_command=True)
     @commands.check(is_trusted)
-    async def block(self, ctx, user: discord.User, notify: bool = False):
+    async def block(self, ctx, user: discord.User, notify: bool = False, *, reason: str = ""):
+        """Stops a user from using TTS Bot, `-block guild` blocks a server
+
+        -block @person true spamming
+        -block guild 123456789012345678 raiding
+        """
         if blocked_users.check(user):
             return await ctx.send(f"{str(user)} | {user.id} is already blocked!")
 
-        blocked_users.add(user)
+        blocked_users.add(user, reason)
 
         await ctx.send(f"Blocked {str(user)} | {str(user.id)}")
         if notify:
             await user.send("You have been blocked from support DMs.\nPossible Reasons: ```Sending invite links\nTrolling\nSpam```")
 
-    @commands.command()
+    @block.command(name="guild", aliases=["server"])
+    @commands.check(is_trusted)
+    async def block_guild(self, ctx, guild_id: int, *, reason: str = ""):
+        """Stops a server from using TTS Bot, and leaves it
+
+        -block guild 123456789012345678 raiding
+        """
+        target = discord.Object(id=guild_id)
+        if blocked_users.check_guild(target):
+            return await ctx.send(f"Guild {guild_id} is already blocked!")
+
+        blocked_users.add_guild(target, reason)
+        await ctx.send(f"Blocked guild {guild_id}")
+
+        guild = self.bot.get_guild(guild_id)
+        if guild is not None:
+            await self.leave_blocked_guild(guild)
+
+    @commands.group(invoke_without_command=True)
     @commands.check(is_trusted)
     async def unblock(self, ctx, user: discord.User, notify: bool = False):
+        """Lets a blocked user use TTS Bot again, `-unblock guild` unblocks a server
+
+        -unblock @person true
+        -unblock guild 123456789012345678
+        """
         if not blocked_users.check(user):
             return await ctx.send(f"{str(user)} | {user.id} isn't blocked!")
 
@@ -247,6 +1275,55 @@ class Main(commands.Cog):
         await ctx.send(f"Unblocked {str(user)} | {str(user.id)}")
         if notify:
             await user.send("You have been unblocked from support DMs.")
+
+    @unblock.command(name="guild", aliases=["server"])
+    @commands.check(is_trusted)
+    async def unblock_guild(self, ctx, guild_id: int):
+        """Lets a blocked server use TTS Bot again
+
+        -unblock guild 123456789012345678
+        """
+        target = discord.Object(id=guild_id)
+        if not blocked_users.check_guild(target):
+            return await ctx.send(f"Guild {guild_id} isn't blocked!")
+
+        blocked_users.remove_guild(target)
+        await ctx.send(f"Unblocked guild {guild_id}")
+
+    async def start_web_server(self):
+        app = web.Application()
+        app.router.add_post("/votes/{bot_list}", self.vote_webhook)
+        self.bot.get_cog("Settings").add_dashboard_routes(app)
+
+        self.bot.web_runner = web.AppRunner(app)
+        await self.bot.web_runner.setup()
+        await web.TCPSite(self.bot.web_runner, port=web_port).start()
+
+    async def vote_webhook(self, request):
+        # top.gg sends the voter as "user", discordbotlist as "id", both send the secret as the Authorization header
+        bot_list = request.match_info["bot_list"]
+        secret = vote_secrets.get(bot_list)
+        # Compared in constant time, so the secret can't be guessed from how long a wrong one takes
+        if not secret or not hmac.compare_digest(request.headers.get("Authorization", "").encode(), secret.encode()):
+            return web.Response(status=401)
+
+        try:
+            data = await request.json()
+            user_id = int(data["user"] if bot_list == "topgg" else data["id"])
+        except (ValueError, KeyError, TypeError):
+            return web.Response(status=400)
+
+        votes.add(discord.Object(id=user_id), config.getint("Votes", "hours", fallback=24))
+        analytics.add(f"{bot_list}_votes")
+        return web.Response(status=204)
+
+    async def leave_blocked_guild(self, guild):
+        reason = blocked_users.guild_reason(guild) or _(guild, "No reason was given")
+
+        try:    await guild.owner.send(_(guild, "{name} has been blocked from being used in {guild_name}, so I have left it.\nReason: {reason}\nIf you think this is a mistake, join the support server: https://discord.gg/zWPWwQC", name=self.bot.user.name, guild_name=guild.name, reason=reason))
+        except (discord.errors.HTTPException, AttributeError):    pass
+
+        await guild.leave()
 #//////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
     @commands.Cog.listener()
     async def on_ready(self):
@@ -258,7 +1335,17 @@ class Main(commands.Cog):
 
         self.bot.queue = dict()
         self.bot.playing = dict()
+        self.bot.announcements = dict()
+        self.bot.last_author = dict()
+        self.bot.last_message = dict()
+        self.bot.paused = dict()
+        self.bot.rate_limits = {"global": basic.token_bucket(config.getint("Limits", "global_messages_per_minute", fallback=600))}
+        self.bot.breakers = {
+            backend_name: basic.circuit_breaker(config.getint("Limits", "backend_failures", fallback=5), config.getint("Limits", "backend_cooldown", fallback=60))
+            for backend_name, configured in (("elevenlabs", elevenlabs_key), ("openai", openai_key), ("sidecar", sidecar_url)) if configured
+        }
         self.bot.channels = dict()
+        self.bot.web_runner = None
         self.bot.trusted = basic.remove_chars(config["Main"]["trusted_ids"], "[", "]", "'").split(", ")
         self.bot.supportserver = self.bot.get_guild(int(config["Main"]["main_server"]))
         config_channel = config["Channels"]
@@ -271,6 +1358,12 @@ class Main(commands.Cog):
         print(f"Starting as {self.bot.user.name}!")
         starting_message = await self.bot.channels["logs"].send(f"Starting {self.bot.user.mention}")
 
+        if web_port:
+            await self.start_web_server()
+
+        for problem in await doctor.check_services(config):
+            await self.bot.channels["errors"].send(problem)
+
         # Load some files
         with open("activity.txt") as f2, open("activitytype.txt") as f3, open("status.txt") as f4:
             activity = f2.read()
@@ -284,14 +1377,79 @@ class Main(commands.Cog):
         for guild in self.bot.guilds:
             self.bot.playing[guild.id] = 0
             self.bot.queue[guild.id] = dict()
+            self.bot.announcements[guild.id] = list()
 
+        saved_queue.expire(config.getint("Limits", "queue_replay_age", fallback=300))
         self.avoid_file_crashes.start()
+        self.send_logs.start()
+        self.trial_reminders.start()
+        self.refresh_voice_lists.start()
+        if any(bot_list_tokens.values()):
+            self.post_stats.change_interval(minutes=config.getint("BotLists", "interval", fallback=30))
+            self.post_stats.start()
+        self.bot.loop.create_task(self.rejoin_voice_channels())
 
         ping = str(time.monotonic() - before).split(".")[0]
         await starting_message.edit(content=f"Started and ready! Took `{ping} seconds`")
 
         last_cached_message = await self.bot.channels["logs"].send("Waiting to chunk a guild!")
 
+    async def connect(self, channel):
+        # Returns False if this is a Stage that I couldn't become a speaker in, only asked to speak
+        await channel.connect()
+        if not isinstance(channel, discord.StageChannel):
+            return True
+
+        me = channel.guild.me
+        try:
+            if channel.permissions_for(me).mute_members:
+                await me.edit(suppress=False)
+                return True
+
+            await me.request_to_speak()
+        except discord.errors.HTTPException:    pass
+
+        return False
+
+    async def follow_member(self, guild, channel):
+        # Moves to the followed person's voice channel, if I'm allowed in and not already joining or leaving one
+        permissions = channel.permissions_for(guild.me)
+        if not permissions.connect or not permissions.speak or basic.get_value(self.bot.playing, guild.id) in (2, 3):
+            return
+        if channel.user_limit and len(channel.members) >= channel.user_limit and not permissions.move_members:
+            return
+
+        if guild.voice_client is not None:
+            return await guild.voice_client.move_to(channel)
+
+        self.bot.playing[guild.id] = 3
+        try:    await self.connect(channel)
+        except (asyncio_TimeoutError, discord.ClientException):  pass
+        self.bot.playing[guild.id] = 0
+
+    async def rejoin_voice_channels(self):
+        for guild_id, channel_id in voice_channels.get_all().items():
+            guild = self.bot.get_guild(guild_id)
+            channel = guild and guild.get_channel(channel_id)
+            if channel is None:
+                voice_channels.remove(discord.Object(id=guild_id))
+                continue
+
+            self.bot.playing[guild.id] = 3
+            try:    await self.connect(channel)
+            except (asyncio_TimeoutError, discord.ClientException, discord.Forbidden):
+                self.bot.playing[guild.id] = 0
+                continue
+
+            self.bot.playing[guild.id] = 0
+
+            setup_channel = guild.get_channel(settings.get(guild, "channel"))
+            if setup_channel is not None:
+                try:    await setup_channel.send(_(guild, "I'm back! I have rejoined {channel} after restarting.", channel=channel.mention))
+                except discord.Forbidden:   pass
+
+            self.bot.loop.create_task(self.replay_queue(guild))
+
     @commands.Cog.listener()
     async def on_message(self, message):
         if message.channel.id == 749971061843558440 and message.embeds and str(message.author) == "GitHub#0000":
@@ -327,17 +1485,12 @@ class Main(commands.Cog):
                         await self.bot.channels['logs'].send('**`SUCCESS`**')
 
         elif message.guild is not None:
-            saythis = message.clean_content.lower()
+            readable = readable_text(message)
+            if readable is None:
+                return
 
-            # Get settings
+            saythis, starts_with_tts = readable
             autojoin = settings.get(message.guild, "auto_join")
-            bot_ignore = settings.get(message.guild, "bot_ignore")
-
-            starts_with_tts = saythis.startswith("-tts")
-
-            # if author is a bot and bot ignore is on
-            if bot_ignore and message.author.bot:
-                return
 
             # if author is not a bot, and is not in a voice channel, and doesn't start with -tts
             if not message.author.bot and message.author.voice is None and starts_with_tts is False:
@@ -347,142 +1500,42 @@ class Main(commands.Cog):
             if message.guild.voice_client is None and autojoin is False:
                 return
 
-            # Check if a setup channel
-            if message.channel.id != settings.get(message.guild, "channel"):
-                return
+            # This line :( | if autojoin is True **or** message starts with -tts **or** author in same voice channel as bot
+            if autojoin or starts_with_tts or message.author.bot or message.author.voice.channel == message.guild.voice_client.channel:
+
+                #Auto Join
+                if message.guild.voice_client is None and autojoin and basic.get_value(self.bot.playing, message.guild.id) in (0, 1):
+                    try:  channel = message.author.voice.channel
+                    except AttributeError: return
+
+                    self.bot.playing[message.guild.id] = 3
+                    await self.connect(channel)
+                    self.bot.playing[message.guild.id] = 0
+                    self.bot.loop.create_task(self.replay_queue(message.guild))
 
-            # If message is **not** empty **or** there is an attachment
-            if int(len(saythis)) != 0 or message.attachments:
-
-                # Ignore messages starting with - that are probably commands (also advertised as a feature when it is wrong lol)
-                if saythis.startswith(BOT_PREFIX) is False or starts_with_tts:
-
-                    # This line :( | if autojoin is True **or** message starts with -tts **or** author in same voice channel as bot
-                    if autojoin or starts_with_tts or message.author.bot or message.author.voice.channel == message.guild.voice_client.channel:
-
-                        #Auto Join
-                        if message.guild.voice_client is None and autojoin and basic.get_value(self.bot.playing, message.guild.id) in (0, 1):
-                            try:  channel = message.author.voice.channel
-                            except AttributeError: return
-
-                            self.bot.playing[message.guild.id] = 3
-                            await channel.connect()
-                            self.bot.playing[message.guild.id] = 0
-
-                        # Sometimes bot.guilds is wrong, because intents
-                        if message.guild.id not in self.bot.queue:
-                            self.bot.queue[message.guild.id] = dict()
-
-                        # Emoji filter
-                        saythis = basic.emojitoword(saythis)
-
-                        # Acronyms and removing -tts
-                        saythis = f" {saythis} "
-                        acronyms = {
-                            "@": " at ",
-                            "irl": "in real life",
-                            "gtg": " got to go ",
-                            "iirc": "if I recall correctly",
-                            "™️": "tm",
-                            "rn": "right now",
-                            "wdym": "what do you mean",
-                            "imo": "in my opinion",
-                        }
-
-                        if starts_with_tts: acronyms["-tts"] = ""
-                        for toreplace, replacewith in acronyms.items():
-                            saythis = saythis.replace(f" {toreplace} ", f" {replacewith} ")
-
-                        saythis = saythis[1:-1]
-                        if saythis == "?":  saythis = "what"
-
-                        # Regex replacements
-                        regex_replacements = {
-                            r"\|\|.*?\|\|": ". spoiler avoided.",
-                            r"```.*?```": ". code block.",
-                            r"`.*?`": ". code snippet.",
-                        }
-
-                        for regex, replacewith in regex_replacements.items():
-                            saythis = re.sub(regex, replacewith, saythis, flags=re.DOTALL)
-
-                        # Url filter
-                        changed = False
-                        for word in saythis.split(" "):
-                            if word.startswith("https://") or word.startswith("http://") or word.startswith("www."):
-                                saythis = saythis.replace(word, "")
-                                changed = True
-
-                        if changed:
-                            saythis += ". This message contained a link"
-
-                        # Toggleable X said and attachment detection
-                        if settings.get(message.guild, "xsaid"):
-                            said_name = settings.nickname.get(message.guild, message.author)
-                            format = basic.exts_to_format(message.attachments)
-
-                            if message.attachments:
-                                if len(saythis) == 0:
-                                    saythis = f"{said_name} sent {format}."
-                                else:
-                                    saythis = f"{said_name} sent {format} and said {saythis}"
-                            else:
-                                saythis = f"{said_name} said: {saythis}"
-
-                        if basic.remove_chars(saythis, " ", "?", ".", ")", "'", '"') == "":
-                            return
-
-                        # Read language file
-                        lang = setlangs.get(message.author)
-
-                        temp_store_for_mp3 = BytesIO()
-                        try:  gTTS.gTTS(text=saythis, lang=lang).write_to_fp(temp_store_for_mp3)
-                        except AssertionError:  return
-                        except (gTTS.tts.gTTSError, ValueError):
-                            return await message.channel.send(f"Ah! gTTS couldn't process {message.jump_url} for some reason, please try again later.")
-
-                        # Discard if over 30 seconds
-                        temp_store_for_mp3.seek(0)
-                        if not (int(MP3(temp_store_for_mp3).info.length) >= 30):
-                            self.bot.queue[message.guild.id][message.id] = temp_store_for_mp3
-                            del temp_store_for_mp3
-
-                        # Queue, please don't touch this, it works somehow
-                        while self.bot.playing[message.guild.id] != 0:
-                            if self.bot.playing[message.guild.id] == 2: return
-                            await asyncio.sleep(0.5)
-
-                        self.bot.playing[message.guild.id] = 1
-
-                        while self.bot.queue[message.guild.id] != dict():
-                            # Sort Queue
-                            self.bot.queue[message.guild.id] = basic.sort_dict(self.bot.queue[message.guild.id])
-
-                            # Select first in queue
-                            message_id_to_read = next(iter(self.bot.queue[message.guild.id]))
-                            selected = self.bot.queue[message.guild.id][message_id_to_read]
-                            selected.seek(0)
-
-                            # Play selected audio
-                            vc = message.guild.voice_client
-                            if vc is not None:
-                                try:    vc.play(FFmpegPCMAudio(selected.read(), pipe=True, options='-loglevel "quiet"'))
-                                except discord.errors.ClientException:  pass # sliences desyncs between discord.py and discord, implement actual fix soon!
-
-                                while vc.is_playing():  await asyncio.sleep(0.5)
-
-                                # Delete said message from queue
-                                if message_id_to_read in self.bot.queue[message.guild.id]:
-                                    del self.bot.queue[message.guild.id][message_id_to_read]
-
-                            else:
-                                # If not in a voice channel anymore, clear the queue
-                                self.bot.queue[message.guild.id] = dict()
-
-                        # Queue should be empty now, let next on_message though
-                        self.bot.playing[message.guild.id] = 0
+                await self.read_message(message, message.guild, saythis, starts_with_tts)
 
         elif message.author.bot is False:
+            # Read DMs in a voice channel shared with the author, if that server allows it
+            saythis = message.clean_content.lower()
+            for vc in self.bot.voice_clients:
+                if message.author.id not in [member.id for member in vc.channel.members]:
+                    continue
+
+                guild = vc.guild
+                member = guild.get_member(message.author.id)
+                setup_channel = guild.get_channel(settings.get(guild, "channel"))
+                required_role = settings.get(guild, "required_role")
+
+                if not settings.get(guild, "allow_dm_tts") or setup_channel is None or saythis.startswith((BOT_PREFIX, *(prefix.lower() for prefix in settings.get(guild, "prefixes")))):
+                    continue
+                if not setup_channel.permissions_for(member).send_messages:
+                    continue
+                if required_role and required_role not in [role.id for role in member.roles]:
+                    continue
+
+                return await self.read_message(message, guild, saythis)
+
             pins = await message.author.pins()
 
             if [True for pinned_message in pins if pinned_message.embeds and pinned_message.embeds[0].title == f"Welcome to {self.bot.user.name} Support DMs!"]:
@@ -511,24 +1564,136 @@ class Main(commands.Cog):
                 await self.bot.channels["logs"].send(f"{str(message.author)} just got the 'Welcome to Support DMs' message")
                 await dm_message.pin()
 
+    async def replied_to(self, message):
+        # Uses discord.py's cache when it can, as fetching every reply would be slow
+        reference = message.reference
+        replied = reference.resolved or reference.cached_message
+        if replied is None:
+            try:    replied = await message.channel.fetch_message(reference.message_id)
+            except discord.errors.HTTPException:    return
+
+        if isinstance(replied, discord.Message):
+            return replied.author
+
+    def unqueue(self, guild_id, message_id):
+        # Returns True if the message was waiting in the queue, and removes it, the one being read is left alone
+        queue = self.bot.queue.get(guild_id, dict())
+        if message_id not in queue or self.reading.get(guild_id) == message_id:
+            return False
+
+        queue.pop(message_id)
+        saved_queue.remove(discord.Object(id=guild_id), message_id)
+        return True
+
+    @commands.Cog.listener()
+    async def on_raw_message_delete(self, payload):
+        if payload.guild_id is not None:
+            self.unqueue(payload.guild_id, payload.message_id)
+
+    @commands.Cog.listener()
+    async def on_raw_message_edit(self, payload):
+        # Edits only change the text, so embeds loading in don't count
+        guild = self.bot.get_guild(int(payload.data.get("guild_id", 0)))
+        if guild is None or "content" not in payload.data or not self.unqueue(guild.id, payload.message_id):
+            return
+
+        try:    message = await guild.get_channel(payload.channel_id).fetch_message(payload.message_id)
+        except (discord.errors.HTTPException, AttributeError):  return
+
+        # The edit may have made it something on_message wouldn't read, such as a command
+        readable = readable_text(message)
+        if readable is not None:
+            await self.read_message(message, guild, *readable, edited=True)
+
+    @commands.Cog.listener()
+    async def on_reaction_add(self, reaction, user):
+        message = reaction.message
+        guild = message.guild
+        if guild is None or user.bot or not settings.get(guild, "read_reactions") or guild.voice_client is None:
+            return
+        if message.channel.id != settings.get(guild, "channel") or (datetime.utcnow() - message.created_at).total_seconds() > 600:
+            return
+        if opted_out.check(user) or blocked_users.check(user) or self.is_paused(guild):
+            return
+
+        # Reactions are collected for a few seconds and read together, so reaction storms don't flood the queue
+        name = settings.nickname.get(guild, user)
+        emoji = basic.emojitoword(str(reaction.emoji)).strip()
+        author = settings.nickname.get(guild, message.author)
+
+        pending = self.pending_reactions.setdefault(guild.id, list())
+        pending.append((name, emoji, author, member_voice(guild, user, message.channel)))
+        if len(pending) == 1:
+            self.bot.loop.create_task(self.read_reactions(guild))
+
+    async def read_reactions(self, guild):
+        await asyncio.sleep(5)
+        pending = self.pending_reactions.pop(guild.id, list())
+
+        text = ". ".join(f"{name} reacted with {emoji} to {author}'s message" for name, emoji, author, lang in pending[:3])
+        if len(pending) > 3:
+            text += f", and {len(pending) - 3} more reactions"
+
+        await self.announce(guild, text, pending[0][3])
+
+    @commands.Cog.listener()
+    async def on_socket_response(self, payload):
+        # Scheduled events are updated to status 2 when they start, and can be updated again while they're running
+        event = payload.get("d")
+        if payload.get("t") not in ("GUILD_SCHEDULED_EVENT_UPDATE", "GUILD_SCHEDULED_EVENT_DELETE"):
+            return
+
+        # Once it has ended, been cancelled or deleted it can't start again, so it doesn't need remembering
+        if payload["t"] == "GUILD_SCHEDULED_EVENT_DELETE" or event.get("status") in (3, 4):
+            return self.started_events.discard(event["id"])
+        if event.get("status") != 2 or event["id"] in self.started_events:
+            return
+
+        self.started_events.add(event["id"])
+        guild = self.bot.get_guild(int(event["guild_id"]))
+        if guild is None or settings.get(guild, "announce_events") == "off":
+            return
+
+        channel = guild.get_channel(settings.get(guild, "channel"))
+        if channel is not None:
+            try:    await channel.send(_(guild, "📅 **{name}** is starting! https://discord.com/events/{guild_id}/{event_id}", name=event["name"], guild_id=guild.id, event_id=event["id"]))
+            except discord.errors.HTTPException:    pass
+
+        if settings.get(guild, "announce_events") == "voice" and guild.voice_client is not None:
+            await self.announce(guild, _(guild, "Event {name} is starting", name=event["name"]), member_voice(guild, guild.me, channel))
+
     @commands.Cog.listener()
     async def on_voice_state_update(self, member, before, after):
         guild = member.guild
         vc = guild.voice_client
         playing = basic.get_value(self.bot.playing, guild.id)
 
-        if member.id == self.bot.user.id:   return # someone other than bot left vc
-        elif not (before.channel and not after.channel):   return # user left voice channel
-        elif not vc:   return # bot in a voice channel
+        if member.id == settings.get(guild, "follow") and after.channel is not None and after.channel != before.channel:
+            await self.follow_member(guild, after.channel)
+            vc = guild.voice_client
 
-        elif len([member for member in vc.channel.members if not member.bot]) != 0:    return # bot is only one left
-        elif playing not in (0, 1):   return # bot not already joining/leaving a voice channel
+        if member.id == self.bot.user.id:
+            # Remember where TTS Bot is, to rejoin after a restart
+            if after.channel:   voice_channels.set(guild, after.channel)
+            else:   voice_channels.remove(guild)
+            return
 
-        else:
+        elif not vc:   return # bot in a voice channel
+
+        # user left voice channel, bot is only one left, and bot not already joining/leaving a voice channel
+        elif before.channel and not after.channel and len([vc_member for vc_member in vc.channel.members if not vc_member.bot]) == 0 and playing in (0, 1):
             self.bot.playing[guild.id] = 2
             await vc.disconnect(force=True)
             self.bot.playing[guild.id] = 0
 
+        elif before.channel != after.channel and not member.bot and settings.get(guild, "announce_joins"):
+            name = settings.nickname.get(guild, member)
+
+            if after.channel == vc.channel:
+                await self.announce(guild, _(guild, "{name} joined", name=name), member_voice(guild, member))
+            elif before.channel == vc.channel:
+                await self.announce(guild, _(guild, "{name} left", name=name), member_voice(guild, member))
+
     @bot.event
     async def on_error(event, *args, **kwargs):
         errors = exc_info()
@@ -538,7 +1703,7 @@ class Main(commands.Cog):
 
             message = await args[0].channel.fetch_message(args[0].id)
             if isinstance(errors[1], discord.errors.Forbidden):
-                try:    return await message.author.send("Unknown Permission Error, please give TTS Bot the required permissions!")
+                try:    return await message.author.send(_(message.guild, "Unknown Permission Error, please give TTS Bot the required permissions!"))
                 except discord.errors.Forbidden:    return
 
             part1 = f"""{message.author} caused an error with the message: {message.content}"""
@@ -550,11 +1715,11 @@ class Main(commands.Cog):
 
     @commands.Cog.listener()
     async def on_command_error(self, ctx, error):
-        if hasattr(ctx.command, 'on_error') or isinstance(error, commands.CommandNotFound) or isinstance(error, commands.NotOwner):
+        if hasattr(ctx.command, 'on_error') or isinstance(error, commands.CommandNotFound) or isinstance(error, commands.NotOwner) or not not_blocked(ctx):
             return
 
         if ctx.guild is not None and not ctx.guild.chunked:
-            message = "**Warning:** The server you are in hasn't been fully loaded yet, this could cause issues!"
+            message = _(ctx.guild, "**Warning:** The server you are in hasn't been fully loaded yet, this could cause issues!")
 
             try:  await ctx.send(message)
             except:
@@ -563,27 +1728,43 @@ class Main(commands.Cog):
 
         error = getattr(error, 'original', error)
 
+        if isinstance(error, BadDuration):
+            bad_duration = _(ctx.guild, "Error: `{argument}` isn't a duration, use something like `30s`, `10m` or `1h30m`!", argument=error.argument)
+            return await ctx.send(f"{bad_duration} {usage_help(ctx.guild, ctx.command)}")
+
+        for not_found, message in not_found_errors.items():
+            if isinstance(error, not_found):
+                not_found = _(ctx.guild, message, argument=error.argument)
+                return await ctx.send(f"{not_found} {usage_help(ctx.guild, ctx.command)}")
+
         for typed_wrong in (commands.BadArgument, commands.MissingRequiredArgument, commands.UnexpectedQuoteError, commands.ExpectedClosingQuoteError):
             if isinstance(error, typed_wrong):
-                return await ctx.send(f"Did you type the command right, {ctx.author.mention}? Try doing -help!")
+                typed_wrong = _(ctx.guild, "Did you type the command right, {mention}?", mention=ctx.author.mention)
+                return await ctx.send(f"{typed_wrong} {usage_help(ctx.guild, ctx.command)}")
 
         for Timeout_Error in (concurrent_TimeoutError, asyncio_TimeoutError):
             if isinstance(error, Timeout_Error):
-                return await ctx.send("**Timeout Error!** Do I have perms to see the channel you are in? (if yes, join https://discord.gg/zWPWwQC and ping Gnome!#6669)")
+                return await ctx.send(_(ctx.guild, "**Timeout Error!** Do I have perms to see the channel you are in? (if yes, join https://discord.gg/zWPWwQC and ping Gnome!#6669)"))
+
+        if isinstance(error, commands.CommandOnCooldown):
+            if ctx.command.name in default_cooldowns:
+                return await ctx.send(_(ctx.guild, "**Error:** This command is on cooldown, try again in {seconds} seconds! Admins can change the cooldown with `-set cooldown {command} seconds`", seconds=round(error.retry_after, 1), command=ctx.command.name))
+
+            return await ctx.send(_(ctx.guild, "**Error:** This command is on cooldown, try again in {seconds} seconds!", seconds=round(error.retry_after, 1)))
 
         if isinstance(error, commands.NoPrivateMessage):
-            return await ctx.author.send("**Error:** This command cannot be used in private messages!")
+            return await ctx.author.send(_(ctx.guild, "**Error:** This command cannot be used in private messages!"))
 
         elif isinstance(error, commands.MissingPermissions):
-            return await ctx.send(f"**Error:** You are missing {error.missing_perms} to run this command!")
+            return await ctx.send(_(ctx.guild, "**Error:** You are missing {permissions} to run this command!", permissions=error.missing_perms))
         elif isinstance(error, commands.BotMissingPermissions):
             if "send_messages" in str(error.missing_perms):
-                return await ctx.author.send("**Error:** I could not complete this command as I don't have send messages permissions!")
+                return await ctx.author.send(_(ctx.guild, "**Error:** I could not complete this command as I don't have send messages permissions!"))
 
-            return await ctx.send(f'**Error:** I am missing the permissions: {basic.remove_chars(error.missing_perms, "[", "]")}')
+            return await ctx.send(_(ctx.guild, "**Error:** I am missing the permissions: {permissions}", permissions=basic.remove_chars(error.missing_perms, "[", "]")))
         elif isinstance(error, discord.errors.Forbidden):
             await self.bot.channels["errors"].send(f"```discord.errors.Forbidden``` caused by {str(ctx.message.content)} sent by {str(ctx.author)}")
-            return await ctx.author.send("Unknown Permission Error, please give TTS Bot the required permissions. If you want this bug fixed, please do `-suggest *what command you just run*`")
+            return await ctx.author.send(_(ctx.guild, "Unknown Permission Error, please give TTS Bot the required permissions. If you want this bug fixed, please do `-suggest *what command you just run*`"))
 
         first_part = f"{str(ctx.author)} caused an error with the message: {ctx.message.clean_content}"
         second_part = ''.join(format_exception(type(error), error, error.__traceback__))
@@ -597,7 +1778,11 @@ class Main(commands.Cog):
 
     @commands.Cog.listener()
     async def on_guild_join(self, guild):
+        if blocked_users.check_guild(guild):
+            return await self.leave_blocked_guild(guild)
+
         self.bot.queue[guild.id] = dict()
+        self.bot.announcements[guild.id] = list()
 
         try:    chunk_guilds.start()
         except RuntimeError:    pass
@@ -605,20 +1790,36 @@ class Main(commands.Cog):
         self.bot.chunk_queue.append(guild.id)
         await asyncio.sleep(5)
 
-        owner = guild.owner
+        # guild.owner is None when the owner isn't cached
+        try:    owner = guild.owner or await self.bot.fetch_user(guild.owner_id)
+        except discord.errors.HTTPException:    owner = None
         await self.bot.channels["servers"].send(f"Just joined {guild.name}! I am now in {str(len(self.bot.guilds))} different servers!".replace("@", "@ "))
 
-        try:    await owner.send(cleandoc(f"""
-            Hello, I am {self.bot.user.name} and I have just joined your server {guild.name}
-            If you want me to start working do `-setup <#text-channel>` and everything will work in there
-            If you want to get support for {self.bot.user.name}, join the support server!\nhttps://discord.gg/zWPWwQC
-            """))
-        except discord.errors.HTTPException:    pass
+        if owner is not None:
+            try:    await owner.send(_(guild, cleandoc("""
+                Hello, I am {name} and I have just joined your server {guild_name}
+                If you want me to start working do `-setup <#text-channel>` and everything will work in there
+                If you want to get support for {name}, join the support server!
+                https://discord.gg/zWPWwQC"""), name=self.bot.user.name, guild_name=guild.name))
+            except discord.errors.HTTPException:    pass
+
+        # Run the setup wizard in the system channel, or with the owner in DMs if I can't talk there
+        system_channel = guild.system_channel
+        if system_channel is not None and system_channel.permissions_for(guild.me).send_messages:
+            destination = system_channel
+            check = lambda message: message.channel == system_channel and isinstance(message.author, discord.Member) and message.author.guild_permissions.administrator
+        else:
+            destination = owner
+            check = lambda message: message.author.id == guild.owner_id and message.guild is None
+
+        # Servers added through a dashboard invite link already have their settings, so they skip the wizard
+        if not self.bot.get_cog("Settings").apply_invite_settings(guild) and destination is not None:
+            self.bot.loop.create_task(self.bot.get_cog("Settings").setup_wizard(guild, destination, check))
 
         try:
-            if owner.id in [member.id for member in self.bot.supportserver.members if not isinstance(member, NoneType)]:
+            if guild.owner_id in [member.id for member in self.bot.supportserver.members if not isinstance(member, NoneType)]:
                 role = self.bot.supportserver.get_role(738009431052386304)
-                await self.bot.supportserver.get_member(owner.id).add_roles(role)
+                await self.bot.supportserver.get_member(guild.owner_id).add_roles(role)
 
                 embed = discord.Embed(description=f"**Role Added:** {role.mention} to {owner.mention}\n**Reason:** Owner of {guild.name}")
                 embed.set_author(name=f"{str(owner)} (ID {owner.id})", icon_url=owner.avatar_url)
@@ -629,57 +1830,151 @@ class Main(commands.Cog):
     @commands.Cog.listener()
     async def on_guild_remove(self, guild):
         settings.remove(guild)
+        settings_audit.remove(guild)
+        analytics.remove(guild)
+        saved_queue.clear(guild)
+        voice_channels.remove(guild)
 
         if guild.id in self.bot.queue:  self.bot.queue.pop(guild.id, None)
         if guild.id in self.bot.playing:  self.bot.playing.pop(guild.id, None)
+        if guild.id in self.bot.announcements:  self.bot.announcements.pop(guild.id, None)
+        if guild.id in self.bot.last_author:  self.bot.last_author.pop(guild.id, None)
+        if guild.id in self.bot.last_message:  self.bot.last_message.pop(guild.id, None)
+        if guild.id in self.bot.rate_limits:  self.bot.rate_limits.pop(guild.id, None)
         await self.bot.channels["servers"].send(f"Just left/got kicked from {str(guild.name)}. I am now in {str(len(self.bot.guilds))} servers".replace("@", "@ "))
 #//////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
     @commands.command()
     async def uptime(self, ctx):
-        await ctx.send(f"{self.bot.user.mention} has been up for {int(monotonic() // 60)} minutes")
+        """Shows how long TTS Bot has been running"""
+        await ctx.send(_(ctx.guild, "{mention} has been up for {minutes} minutes", mention=self.bot.user.mention, minutes=int(monotonic() // 60)))
 
-    @commands.command()
+    @commands.group(invoke_without_command=True)
     async def debug(self, ctx):
+        """Sends you debug info about this server's queue"""
         with open("queue.txt", "w") as f:   f.write(str(self.bot.queue[ctx.guild.id]))
         await ctx.author.send(
-            cleandoc(f"""
+            _(ctx.guild, cleandoc("""
                 **TTS Bot debug info!**
-                Playing is currently set to {str(self.bot.playing[ctx.guild.id])}
-                Guild is chunked: {str(ctx.guild.chunked)}
-                Queue for {ctx.guild.name} | {ctx.guild.id} is attached:
-            """),
+                Playing is currently set to {playing}
+                Guild is chunked: {chunked}
+                Queue for {guild_name} | {guild_id} is attached:
+            """), playing=str(self.bot.playing[ctx.guild.id]), chunked=str(ctx.guild.chunked), guild_name=ctx.guild.name, guild_id=ctx.guild.id),
             file=discord.File("queue.txt"))
 
+    @debug.command(name="guild")
+    @commands.is_owner()
+    async def debug_guild(self, ctx, guild_id: int):
+        """Sends you debug info about another server"""
+        guild = self.bot.get_guild(guild_id)
+        if guild is None:
+            return await ctx.send(f"I am not in {guild_id}!")
+
+        vc = guild.voice_client
+        rate_limit = self.bot.rate_limits.get(guild.id)
+        changed_settings = {key: value for key, value in settings.get_all(guild).items() if value != default_settings[key]}
+
+        info = cleandoc(f"""
+            {guild.name} | {guild.id}
+            Owner: {guild.owner} | {guild.owner_id}
+            Members: {guild.member_count} | Chunked: {guild.chunked} | Blocked: {blocked_users.check_guild(guild)}
+            Voice channel: {vc.channel if vc else None} | Playing: {self.bot.playing.get(guild.id)}
+            Queue: {list(self.bot.queue.get(guild.id, dict()).keys())}
+            Announcements: {len(self.bot.announcements.get(guild.id, list()))}
+            Rate limit tokens: {rate_limit.tokens if rate_limit else None}
+        """)
+        info += f"\nChanged settings: {json.dumps(changed_settings, indent=4)}"
+
+        await ctx.author.send(file=discord.File(BytesIO(info.encode()), filename=f"{guild.id}_debug.txt"))
+
+    @debug.command(name="cache")
+    @commands.is_owner()
+    async def debug_cache(self, ctx):
+        """Sends you how much TTS Bot is keeping in memory"""
+        queues = self.bot.queue.values()
+        files = ("settings.json", "setlangs.json", "blocked_users.json", "settings_audit.json", "analytics.json", "saved_queue.json", "voice_channels.json", "opted_out.json")
+
+        info = cleandoc(f"""
+            Guilds: {len(self.bot.guilds)} | Users: {len(self.bot.users)} | Voice clients: {len(self.bot.voice_clients)}
+            Queued messages: {sum(len(queue) for queue in queues)} | Still synthesising: {sum(isinstance(entry, asyncio.Future) for queue in queues for entry in queue.values())}
+            Announcements: {sum(len(announcements) for announcements in self.bot.announcements.values())}
+            Last messages: {sum(len(last_messages) for last_messages in self.bot.last_message.values())}
+            Rate limit buckets: {len(self.bot.rate_limits)} | Chunk queue: {len(self.bot.chunk_queue)}
+            Circuit breakers: {", ".join(f"{name}: {'open' if breaker.is_open() else 'closed'}" for name, breaker in self.bot.breakers.items()) or None}
+        """)
+        info += "\n" + "\n".join(f"{file}: {getsize(file):,} bytes" for file in files if exists(file))
+
+        await ctx.author.send(file=discord.File(BytesIO(info.encode()), filename="cache_debug.txt"))
+
     @commands.check(require_chunk)
-    @commands.bot_has_permissions(read_messages=True, send_messages=True, embed_links=True)
+    @commands.bot_has_permissions(read_messages=True, send_messages=True, embed_links=True, add_reactions=True)
     @commands.command(aliases=["commands"])
-    async def help(self, ctx):
-        message = """
-          `-setup #textchannel`: Setup the bot to read messages from that channel
-
-          `-join`: Joins the voice channel you're in
-          `-leave`: Leaves voice channel
-
-          `-settings`: Display the current settings
-          `-settings help`: Displays list of available settings
-          `-set property value`: Sets a setting
-          """
-        message1 = """
-          `-help`: Shows this message
-          `-botstats`: Shows various different stats
-          `-donate`: Help improve TTS Bot's development and hosting through Patreon
-          `-suggest *suggestion*`: Suggests a new feature! (could also DM TTS Bot)
-          `-invite`: Sends the instructions to invite TTS Bot!"""
-
-        embed=discord.Embed(title="TTS Bot Help!", url="https://discord.gg/zWPWwQC", description=cleandoc(message), color=0x3498db)
-        embed.add_field(name="Universal Commands", value=cleandoc(message1), inline=False)
-        embed.set_footer(text="Do you want to get support for TTS Bot or invite it to your own server? https://discord.gg/zWPWwQC")
-        await ctx.send(embed=embed)
+    async def help(self, ctx, *, command: str = None):
+        """Shows every command, or more about one command
+
+        -help
+        -help set volume
+        """
+        if command is not None:
+            found = self.bot.get_command(command.lower().lstrip("-"))
+            if found is None or found.hidden:
+                return await ctx.send(_(ctx.guild, "Error: There is no `{command}` command, do `-help` to see them all!", command=command))
+
+            return await ctx.send(embed=self.command_embed(ctx, found))
+
+        is_owner = await self.bot.is_owner(ctx.author) or str(ctx.author.id) in self.bot.trusted
+        categories = {category: list() for category in ("General", *help_categories, "Owner")}
+        for found in sorted(self.bot.commands, key=lambda found: found.name):
+            if not found.hidden:    categories[help_category(found)].append(found)
+
+        if not is_owner:    del categories["Owner"]
+
+        embeds = list()
+        for page, (category, found_commands) in enumerate(categories.items(), start=1):
+            lines = [f"`{command_usage(found)}`" + (f": {_(ctx.guild, command_help(found)[0])}" if found.help else "") for found in found_commands]
+
+            embed = discord.Embed(title=_(ctx.guild, "TTS Bot Help: {category}", category=_(ctx.guild, category)), url="https://discord.gg/zWPWwQC", description="\n".join(lines), color=0x3498db)
+            for group in found_commands:
+                if isinstance(group, commands.Group):
+                    embed.add_field(name=f"-{group.name}", value=", ".join(f"`{subcommand.name}`" for subcommand in sorted(group.commands, key=lambda subcommand: subcommand.name)), inline=False)
+
+            embed.set_footer(text=_(ctx.guild, "Page {page}/{pages} | Do -help command for examples and permissions | Support: https://discord.gg/zWPWwQC", page=page, pages=len(categories)))
+            embeds.append(embed)
+
+        await basic.paginate(ctx, embeds)
+
+    def command_embed(self, ctx, command):
+        description, examples = command_help(command)
+
+        embed = discord.Embed(title=command_usage(command), url="https://discord.gg/zWPWwQC", color=0x3498db)
+        embed.description = _(ctx.guild, description) if description else _(ctx.guild, "No description")
+
+        if command.aliases:
+            embed.add_field(name=_(ctx.guild, "Aliases"), value=", ".join(f"`{alias}`" for alias in command.aliases), inline=False)
+        if examples:
+            embed.add_field(name=_(ctx.guild, "Examples"), value="\n".join(f"`{example}`" for example in examples), inline=False)
+
+        needed = check_permissions(command, "has_permissions") + [_(ctx.guild, check_descriptions[check.__qualname__]) for check in command.checks if check.__qualname__ in check_descriptions]
+        if needed:
+            embed.add_field(name=_(ctx.guild, "You need"), value=", ".join(needed), inline=False)
+
+        bot_needed = check_permissions(command, "bot_has_permissions")
+        if bot_needed:
+            embed.add_field(name=_(ctx.guild, "TTS Bot needs"), value=", ".join(bot_needed), inline=False)
+
+        if command.name in default_cooldowns and ctx.guild is not None:
+            seconds = settings.get(ctx.guild, "cooldowns").get(command.name, default_cooldowns[command.name])
+            embed.add_field(name=_(ctx.guild, "Cooldown"), value=_(ctx.guild, "{seconds} seconds, change it with `-set cooldown`", seconds=seconds), inline=False)
+
+        if isinstance(command, commands.Group):
+            embed.add_field(name=_(ctx.guild, "Subcommands"), value=", ".join(f"`{subcommand.name}`" for subcommand in sorted(command.commands, key=lambda subcommand: subcommand.name)), inline=False)
+
+        return embed
 
     @commands.check(require_chunk)
     @commands.bot_has_permissions(read_messages=True, send_messages=True, embed_links=True)
     @commands.command(aliases=["botstats", "stats"])
     async def info(self, ctx):
+        """Shows various different stats"""
         channels = int()
         for guild in self.bot.guilds:
             try:
@@ -688,161 +1983,868 @@ class Main(commands.Cog):
             except:
                 pass
 
-        main_section = cleandoc(f"""
+        main_section = _(ctx.guild, cleandoc("""
           Currently in:
-            :small_blue_diamond: {str(channels)} voice channels
-            :small_orange_diamond: {len(self.bot.guilds)} servers
-          and can be used by {sum([guild.member_count for guild in self.bot.guilds]):,} people!
-        """)
+            :small_blue_diamond: {channels} voice channels
+            :small_orange_diamond: {guilds} servers
+          and can be used by {members} people!
+        """), channels=str(channels), guilds=len(self.bot.guilds), members=f"{sum([guild.member_count for guild in self.bot.guilds]):,}")
+        main_section += "\n" + _(ctx.guild, "I have read {messages} messages, made of {characters} characters!", messages=f"{analytics.get('messages'):,}", characters=f"{analytics.get('characters'):,}")
+
+        for backend_name, breaker in self.bot.breakers.items():
+            if breaker.is_open():
+                state = _(ctx.guild, "Skipped for {seconds} more seconds", seconds=int(breaker.remaining()))
+            else:
+                state = _(ctx.guild, "Working")
+
+            main_section += f"\n:small_blue_diamond: {backend_name}: {state}"
 
-        footer = cleandoc("""
+        footer = _(ctx.guild, cleandoc("""
             Support Server: https://discord.gg/zWPWwQC
             Repository: https://github.com/Gnome-py/Discord-TTS-Bot
-        """)
+        """))
 
-        embed=discord.Embed(title=f"{self.bot.user.name}: Now open source!", description=main_section, url="https://discord.gg/zWPWwQC", color=0x3498db)
+        embed=discord.Embed(title=_(ctx.guild, "{name}: Now open source!", name=self.bot.user.name), description=main_section, url="https://discord.gg/zWPWwQC", color=0x3498db)
         embed.set_footer(text=footer)
         embed.set_thumbnail(url=str(self.bot.user.avatar_url))
 
         await ctx.send(embed=embed)
 
     @commands.guild_only()
-    @commands.check(require_chunk)
-    @commands.bot_has_permissions(read_messages=True, send_messages=True)
+    @commands.bot_has_permissions(read_messages=True, send_messages=True, embed_links=True)
     @commands.command()
-    async def join(self, ctx):
-        if basic.get_value(self.bot.playing, ctx.guild.id) == 3:
-            return await ctx.send("Error: Already trying to join your voice channel!")
+    async def usage(self, ctx):
+        """Shows how much TTS Bot has been used in this server"""
+        days = analytics.get_guild(ctx.guild)
+        most_messages = max(day["messages"] for _day, day in days) or 1
+
+        graph = list()
+        for day_name, day in days:
+            bar = "█" * round(day["messages"] / most_messages * 20)
+            graph.append(f"{day_name[5:]} {bar} {day['messages']}")
+
+        messages = sum(day["messages"] for _day, day in days)
+        characters = sum(day["characters"] for _day, day in days)
+        speakers = len({speaker for _day, day in days for speaker in day["speakers"]})
+
+        embed = discord.Embed(title=_(ctx.guild, "TTS Bot usage for {guild_name}", guild_name=ctx.guild.name), url="https://discord.gg/zWPWwQC", color=0x3498db)
+        embed.description = "```\n" + "\n".join(graph) + "\n```"
+        embed.add_field(name=_(ctx.guild, "Last 7 days"), value=_(ctx.guild, "{messages} messages, {characters} characters, {speakers} speakers", messages=f"{messages:,}", characters=f"{characters:,}", speakers=speakers))
+        await ctx.send(embed=embed)
 
+    def join_failure(self, ctx):
+        # Returns the first reason from join_failures that stops me joining the author's voice channel, or None
+        if basic.get_value(self.bot.playing, ctx.guild.id) == 3:
+            return "joining"
         if ctx.channel.id != settings.get(ctx.guild, "channel"):
-            return await ctx.send("Error: Wrong channel, do -channel get the channel that has been setup.")
-
+            return "wrong_channel"
         if ctx.author.voice is None:
-            return await ctx.send("Error: You need to be in a voice channel to make me join your voice channel!")
+            return "not_in_voice"
 
         channel = ctx.author.voice.channel
-        permissions = channel.permissions_for(ctx.guild.me)
+        voice_client = ctx.guild.voice_client
+        if voice_client is not None:
+            return "already_here" if voice_client.channel == channel else "in_other_channel"
 
+        permissions = channel.permissions_for(ctx.guild.me)
         if not permissions.view_channel:
-            return await ctx.send("Error: Missing Permission to view your voice channel!")
-
-        if not permissions.speak or not permissions.use_voice_activation:
-            return await ctx.send("Error: I do not have permssion to speak!")
-
-        if ctx.guild.voice_client is not None and ctx.guild.voice_client == channel:
-            return await ctx.send("Error: I am already in your voice channel!")
+            return "cant_view"
+        if not permissions.connect:
+            return "cant_connect"
+        if channel.user_limit and len(channel.members) >= channel.user_limit and not permissions.move_members:
+            return "full"
+
+        if isinstance(channel, discord.StageChannel):
+            if not permissions.mute_members and not permissions.request_to_speak:
+                return "stage_cant_speak"
+        elif not permissions.speak:
+            return "cant_speak"
+        elif not permissions.use_voice_activation:
+            return "cant_use_vad"
 
-        if ctx.guild.voice_client is not None and ctx.guild.voice_client != channel:
-            return await ctx.send("Error: I am already in a voice channel!")
+    @commands.guild_only()
+    @commands.check(require_chunk)
+    @commands.bot_has_permissions(read_messages=True, send_messages=True)
+    @commands.command()
+    async def join(self, ctx):
+        """Joins the voice channel you're in"""
+        failure = self.join_failure(ctx)
+        if failure is not None:
+            return await ctx.send(_(ctx.guild, join_failures[failure], channel=getattr(ctx.guild.voice_client, "channel", None)))
 
         self.bot.playing[ctx.guild.id] = 3
-        await channel.connect()
+        try:    speaking = await self.connect(ctx.author.voice.channel)
+        except (asyncio_TimeoutError, discord.ClientException) as error:
+            self.bot.playing[ctx.guild.id] = 0
+            return await ctx.send(_(ctx.guild, join_failures["in_other_channel" if isinstance(error, discord.ClientException) else "timed_out"], channel=getattr(ctx.guild.voice_client, "channel", None)))
+
         self.bot.playing[ctx.guild.id] = 0
 
-        await ctx.send("Joined your voice channel!")
+        if speaking:
+            await ctx.send(_(ctx.guild, "Joined your voice channel!"))
+        else:
+            await ctx.send(_(ctx.guild, "Joined your Stage! I've asked to speak, a Stage Moderator needs to invite me up before I can read messages."))
+        self.bot.loop.create_task(self.replay_queue(ctx.guild))
 
     @commands.guild_only()
     @commands.check(require_chunk)
     @commands.bot_has_permissions(send_messages=True)
     @commands.command()
     async def leave(self, ctx):
+        """Leaves the voice channel"""
         if basic.get_value(self.bot.playing, ctx.guild.id) == 2:
-            return await ctx.send("Error: Already trying to leave your voice channel!")
+            return await ctx.send(_(ctx.guild, "Error: Already trying to leave your voice channel!"))
 
         if ctx.channel.id != settings.get(ctx.guild, "channel"):
-            return await ctx.send("Error: Wrong channel, do -channel get the channel that has been setup.")
+            return await ctx.send(_(ctx.guild, "Error: Wrong channel, do -channel get the channel that has been setup."))
 
         if basic.get_value(self.bot.playing, ctx.guild.id) == 3:
-            return await ctx.send("Error: Trying to join a voice channel!")
+            return await ctx.send(_(ctx.guild, "Error: Trying to join a voice channel!"))
 
         elif ctx.author.voice is None:
-            return await ctx.send("Error: You need to be in a voice channel to make me leave!")
+            return await ctx.send(_(ctx.guild, "Error: You need to be in a voice channel to make me leave!"))
 
         elif ctx.guild.voice_client is None:
-            return await ctx.send("Error: How do I leave a voice channel if I am not in one?")
+            return await ctx.send(_(ctx.guild, "Error: How do I leave a voice channel if I am not in one?"))
 
         elif ctx.author.voice.channel != ctx.guild.voice_client.channel:
-            return await ctx.send("Error: You need to be in the same voice channel as me to make me leave!")
+            return await ctx.send(_(ctx.guild, "Error: You need to be in the same voice channel as me to make me leave!"))
 
         self.bot.playing[ctx.guild.id] = 2
         await ctx.guild.voice_client.disconnect(force=True)
         self.bot.playing[ctx.guild.id] = 0
 
-        await ctx.send("Left voice channel!")
+        await ctx.send(_(ctx.guild, "Left voice channel!"))
 
     @commands.guild_only()
     @commands.check(require_chunk)
     @commands.bot_has_permissions(read_messages=True, send_messages=True)
     @commands.command()
     async def channel(self, ctx):
+        """Shows which channel has been setup"""
         channel = settings.get(ctx.guild, "channel")
 
         if channel == ctx.channel.id:
-            await ctx.send("You are in the right channel already!")
+            await ctx.send(_(ctx.guild, "You are in the right channel already!"))
         elif channel != 0:
-            await ctx.send(f"The current setup channel is: <#{channel}>")
+            await ctx.send(_(ctx.guild, "The current setup channel is: <#{channel}>", channel=channel))
         else:
-            await ctx.send("The channel hasn't been setup, do `-setup #textchannel`")
+            await ctx.send(_(ctx.guild, "The channel hasn't been setup, do `-setup #textchannel`"))
 
+    @commands.guild_only()
     @commands.check(require_chunk)
     @commands.bot_has_permissions(read_messages=True, send_messages=True)
     @commands.command()
-    async def tts(self, ctx):
-        if ctx.message != f"{BOT_PREFIX}tts":
-            await ctx.send(f"You don't need to do `-tts`! {self.bot.user.mention} is made to TTS any message, and ignore messages starting with `-`!")
+    async def tts(self, ctx, *, text: commands.clean_content = None):
+        """Reads the text in your voice channel, even from outside the setup channel
+
+        -tts hello everyone
+        """
+        if text is None:
+            return await ctx.send(_(ctx.guild, "You don't need to do `-tts`! {mention} is made to TTS any message, and ignore messages starting with `-`!", mention=self.bot.user.mention))
+
+        # -tts in the setup channel is read by on_message
+        if ctx.channel.id == settings.get(ctx.guild, "channel"):
+            return
+
+        if ctx.guild.voice_client is None or ctx.author.voice is None or ctx.author.voice.channel != ctx.guild.voice_client.channel:
+            return await ctx.send(_(ctx.guild, "Error: You need to be in my voice channel to use -tts outside of the setup channel!"))
+
+        required_role = settings.get(ctx.guild, "required_role")
+        if required_role and required_role not in [role.id for role in ctx.author.roles]:
+            return await ctx.send(_(ctx.guild, "Error: You need the required role to use -tts!"))
+
+        # Only outside the setup channel, as messages there aren't limited either
+        guild_cooldown(ctx)
+
+        await self.read_message(ctx.message, ctx.guild, text.lower())
+
+    @commands.guild_only()
+    @commands.check(require_chunk)
+    @commands.bot_has_permissions(read_messages=True, read_message_history=True, send_messages=True)
+    @commands.command(aliases=["read_this", "readaloud"])
+    async def read(self, ctx, message: discord.Message = None):
+        """Reply to a message with this to read it in your voice channel, or give a link to it"""
+        # Reply to a message with -read, or give a message link/ID
+        if message is None and ctx.message.reference is not None:
+            message = ctx.message.reference.resolved
+            if message is None:
+                try:    message = await ctx.channel.fetch_message(ctx.message.reference.message_id)
+                except discord.NotFound:    pass
+
+        if not isinstance(message, discord.Message):
+            return await ctx.send(_(ctx.guild, "Error: Reply to a message with -read, or give me a link to it!"))
+
+        if message.guild != ctx.guild:
+            return await ctx.send(_(ctx.guild, "Error: I can only read messages from this server!"))
+
+        if not message.channel.permissions_for(ctx.author).read_messages:
+            return await ctx.send(_(ctx.guild, "Error: You can't see that message!"))
+
+        if ctx.guild.voice_client is None or ctx.author.voice is None or ctx.author.voice.channel != ctx.guild.voice_client.channel:
+            return await ctx.send(_(ctx.guild, "Error: You need to be in my voice channel to use -read!"))
+
+        guild_cooldown(ctx)
+
+        await self.read_message(message, ctx.guild, message.clean_content.lower())
+
+    @commands.guild_only()
+    @commands.bot_has_permissions(send_messages=True)
+    @commands.command(aliases=["check_perms", "permissions"])
+    async def checkperms(self, ctx):
+        """Checks I have every permission I need in the setup and voice channel"""
+        # Checks the setup channel, and my voice channel or else the author's
+        setup_channel = ctx.guild.get_channel(settings.get(ctx.guild, "channel"))
+        voice_channel = getattr(ctx.guild.voice_client, "channel", None) or getattr(ctx.author.voice, "channel", None)
+
+        lines = list()
+        for channel, permissions, missing_channel in (
+            (setup_channel, text_permissions, _(ctx.guild, "The setup channel hasn't been set, do `-setup #textchannel`")),
+            (voice_channel, voice_permissions, _(ctx.guild, "Join a voice channel to check it too")),
+        ):
+            if channel is None:
+                lines.append(f"❔ {missing_channel}")
+                continue
+
+            lines.append(f"**{channel.name}**")
+            channel_permissions = channel.permissions_for(ctx.guild.me)
+            for permission, needed_for in permissions:
+                if getattr(channel_permissions, permission):
+                    lines.append(f"✅ `{permission}`")
+                else:
+                    lines.append(_(ctx.guild, "❌ `{permission}`: needed for {needed_for}, give it to my role in the channel settings", permission=permission, needed_for=_(ctx.guild, needed_for)))
+
+        await ctx.send("\n".join(lines))
+
+    @commands.guild_only()
+    @commands.check(can_pause)
+    @commands.bot_has_permissions(send_messages=True)
+    @commands.command(aliases=["mute", "stop"])
+    async def pause(self, ctx, duration: Duration("m") = None):
+        """Stops reading messages without leaving, until `-resume`, or for a while
+
+        -pause
+        -pause 1h30m
+        """
+        if duration is not None and not 60 <= duration <= 86400:
+            return await ctx.send(_(ctx.guild, "Error: You can only pause for between 1 minute and 24 hours!"))
+
+        self.bot.paused[ctx.guild.id] = None if duration is None else time.monotonic() + duration
+
+        # Stop what's being read, without leaving the voice channel
+        self.bot.queue[ctx.guild.id] = dict()
+        self.bot.announcements[ctx.guild.id] = list()
+        saved_queue.clear(ctx.guild)
+        if ctx.guild.voice_client is not None:
+            ctx.guild.voice_client.stop()
+
+        if duration is None:
+            await ctx.send(_(ctx.guild, "Paused! I won't read any messages until someone does `-resume`."))
+        else:
+            await ctx.send(_(ctx.guild, "Paused! I won't read any messages for {duration}, or until someone does `-resume`.", duration=basic.describe_duration(duration)))
+
+    @commands.guild_only()
+    @commands.check(can_pause)
+    @commands.bot_has_permissions(send_messages=True)
+    @commands.command(aliases=["unmute", "unpause"])
+    async def resume(self, ctx):
+        """Starts reading messages again after `-pause`"""
+        if not self.is_paused(ctx.guild):
+            return await ctx.send(_(ctx.guild, "Error: I'm not paused!"))
+
+        self.bot.paused.pop(ctx.guild.id, None)
+        await ctx.send(_(ctx.guild, "Resumed! I will read messages again."))
+
+    @commands.bot_has_permissions(send_messages=True)
+    @commands.command(aliases=["opt_out"])
+    async def optout(self, ctx):
+        """Stops TTS Bot from ever reading your messages, in every server"""
+        if opted_out.check(ctx.author):
+            return await ctx.send(_(ctx.guild, "Error: You have already opted out, do -optin to have your messages read again!"))
+
+        opted_out.add(ctx.author)
+        analytics.remove_user(ctx.author)
+        saved_queue.remove_user(ctx.author)
+        for last_messages in self.bot.last_message.values():
+            last_messages.pop(ctx.author.id, None)
+
+        await ctx.send(_(ctx.guild, "Opted out! I will never read or store your messages in any server, do -optin to undo this."))
+
+    @commands.bot_has_permissions(send_messages=True)
+    @commands.command(aliases=["opt_in"])
+    async def optin(self, ctx):
+        """Lets TTS Bot read your messages again after `-optout`"""
+        if not opted_out.check(ctx.author):
+            return await ctx.send(_(ctx.guild, "Error: You haven't opted out!"))
+
+        opted_out.remove(ctx.author)
+        await ctx.send(_(ctx.guild, "Opted in! Your messages will be read again."))
+
+    @commands.bot_has_permissions(send_messages=True)
+    @commands.command(aliases=["upvote"])
+    async def vote(self, ctx):
+        """Vote for TTS Bot to get premium voices for your messages"""
+        links = {
+            "topgg": f"https://top.gg/bot/{self.bot.user.id}/vote",
+            "dbl": f"https://discordbotlist.com/bots/{self.bot.user.id}/upvote",
+        }
+        links = [link for bot_list, link in links.items() if vote_secrets[bot_list]]
+        if not links:
+            return await ctx.send(_(ctx.guild, "Error: Voting isn't set up for this bot!"))
+
+        remaining = int(votes.remaining(ctx.author))
+        if remaining:
+            perks = _(ctx.guild, "Thanks for voting! Your messages use premium voices for another {hours} hours and {minutes} minutes.", hours=remaining // 3600, minutes=remaining % 3600 // 60)
+        else:
+            perks = _(ctx.guild, "Vote to have your messages read with premium voices for {hours} hours!", hours=config.getint("Votes", "hours", fallback=24))
+
+        await ctx.send(perks + "\n" + "\n".join(links))
+
+    async def send_feedback(self, ctx, kind, text):
+        # Near copies of recent feedback are dropped, so the same thing isn't spammed
+        if any(SequenceMatcher(None, text.lower(), recent).ratio() > 0.8 for recent in self.recent_feedback):
+            return await ctx.send(_(ctx.guild, "Error: That has already been sent recently, thanks!"))
+
+        embed = discord.Embed(title=kind.capitalize(), description=text, color=0x3498db)
+        embed.set_author(name=str(ctx.author), icon_url=str(ctx.author.avatar_url))
+        embed.add_field(name="Server", value=f"{ctx.guild.name} | {ctx.guild.id}" if ctx.guild else "DMs")
+        embed.set_footer(text=f"User ID: {ctx.author.id}")
+
+        try:
+            if feedback_webhooks[kind]:
+                async with aiohttp.ClientSession() as session:
+                    webhook = discord.Webhook.from_url(feedback_webhooks[kind], adapter=discord.AsyncWebhookAdapter(session))
+                    await webhook.send(embed=embed, username=self.bot.user.name, avatar_url=str(self.bot.user.avatar_url))
+            else:
+                await self.bot.channels["suggestions"].send(embed=embed)
+        except (discord.errors.HTTPException, aiohttp.ClientError):
+            return await ctx.send(_(ctx.guild, "Error: I couldn't send that, please try again later!"))
+
+        self.recent_feedback.append(text.lower())
+        await ctx.send(_(ctx.guild, "Thanks! Your {kind} has been sent to the developers, you may get a reply in your DMs.", kind=_(ctx.guild, kind)))
+
+    @commands.cooldown(1, 60, commands.BucketType.user)
+    @commands.bot_has_permissions(send_messages=True)
+    @commands.command(aliases=["suggestion"])
+    async def suggest(self, ctx, *, text: str):
+        """Suggests a new feature! (could also DM TTS Bot)
+
+        -suggest a voice that sounds like a pirate
+        """
+        await self.send_feedback(ctx, "suggestion", text)
+
+    @commands.cooldown(1, 60, commands.BucketType.user)
+    @commands.bot_has_permissions(send_messages=True)
+    @commands.command(aliases=["bug_report", "report"])
+    async def bug(self, ctx, *, text: str):
+        """Reports a bug to the developers
+
+        -bug my messages are read twice
+        """
+        await self.send_feedback(ctx, "bug report", text)
+
+    @commands.is_owner()
+    @commands.command(aliases=["reply_feedback"])
+    async def reply(self, ctx, *, text: str):
+        """Reply to a suggestion or bug report with this to DM the person who sent it"""
+        # Reply to a suggestion or bug report in its channel, the person who sent it gets the reply in their DMs
+        reference = ctx.message.reference
+        try:    feedback = reference and (reference.resolved or await ctx.channel.fetch_message(reference.message_id))
+        except discord.NotFound:    feedback = None
+
+        # Embeds without a footer have Embed.Empty instead of a string
+        footer = feedback.embeds[0].footer.text if feedback and feedback.embeds else None
+        if not isinstance(footer, str) or not footer.startswith("User ID: "):
+            return await ctx.send("Error: Reply to a suggestion or bug report with -reply!")
+
+        embed = feedback.embeds[0]
+        user = self.bot.get_user(int(footer[len("User ID: "):]))
+        if user is None:
+            return await ctx.send("Error: I can't find the person who sent that!")
+
+        try:    await user.send(f"**A developer replied to your {embed.title.lower()}:**\n> {embed.description[:500]}\n{text}")
+        except discord.errors.Forbidden:    return await ctx.send(f"Error: {user} has their DMs closed!")
+
+        await ctx.message.add_reaction("✅")
+
+    @commands.bot_has_permissions(send_messages=True, add_reactions=True)
+    @commands.command(aliases=["my_data"])
+    async def mydata(self, ctx, action: str = None):
+        """Sends you or deletes all the data TTS Bot has stored about you
+
+        -mydata export
+        -mydata delete
+        """
+        if action == "export":
+            exported = {name: store.export_user(ctx.author) for name, store in user_data.items()}
+            exported["opted_out"] = opted_out.check(ctx.author)
+
+            file = discord.File(BytesIO(json.dumps(exported, indent=4).encode()), filename=f"{ctx.author.id}_data.json")
+            try:    await ctx.author.send(_(ctx.guild, "Here is all the data I have stored about you!"), file=file)
+            except discord.Forbidden:
+                return await ctx.send(_(ctx.guild, "Error: I couldn't DM you, please enable DMs from server members!"))
+
+            if ctx.guild is not None:
+                await ctx.send(_(ctx.guild, "Sent your data in DMs!"))
+
+        elif action == "delete":
+            confirm = await ctx.send(_(ctx.guild, "Are you sure you want to delete all the data I have stored about you? This can't be undone, react with ✅ within 30 seconds to confirm. Your -optout will be kept."))
+            await confirm.add_reaction("✅")
+
+            check = lambda reaction, user: user == ctx.author and reaction.message.id == confirm.id and str(reaction.emoji) == "✅"
+            try:    await self.bot.wait_for("reaction_add", check=check, timeout=30)
+            except asyncio_TimeoutError:
+                return await ctx.send(_(ctx.guild, "Cancelled, nothing has been deleted."))
+
+            for store in user_data.values():
+                store.remove_user(ctx.author)
+            for last_messages in self.bot.last_message.values():
+                last_messages.pop(ctx.author.id, None)
+
+            await ctx.send(_(ctx.guild, "Deleted all the data I have stored about you!"))
+
+        else:
+            await ctx.send(_(ctx.guild, "Error: Do `-mydata export` to get a copy of your data, or `-mydata delete` to delete it!"))
+
+    @commands.guild_only()
+    @commands.has_permissions(administrator=True)
+    @commands.bot_has_permissions(send_messages=True)
+    @commands.command()
+    async def trial(self, ctx):
+        """Gives this server 7 days of premium, once"""
+        if is_premium(ctx.guild):
+            return await ctx.send(_(ctx.guild, "Error: This server already has premium!"))
+        if trials.used(ctx.guild):
+            return await ctx.send(_(ctx.guild, "Error: This server has already used its premium trial!"))
+
+        expires = trials.start(ctx.guild)
+        await ctx.send(_(ctx.guild, "Started a 7 day premium trial! It ends on {date}.", date=datetime.utcfromtimestamp(expires).strftime("%Y-%m-%d %H:%M UTC")))
 
 class Settings(commands.Cog):
     def __init__(self, bot):
         self.bot = bot
+        self.dashboard_users = dict()
+        self.invite_settings = dict()
+
+    def add_dashboard_routes(self, app):
+        app.router.add_get("/api/settings", self.dashboard_setting_info)
+        app.router.add_get("/api/guilds/{guild_id}/settings", self.dashboard_get_settings)
+        app.router.add_patch("/api/guilds/{guild_id}/settings", self.dashboard_set_settings)
+        app.router.add_get("/api/guilds/{guild_id}/channels", self.dashboard_channels)
+        app.router.add_get("/api/guilds/{guild_id}/events", self.dashboard_events)
+        if oauth2_secret and oauth2_redirect:
+            app.router.add_get("/invite", self.invite_redirect)
+            app.router.add_get("/invite/callback", self.invite_callback)
+
+    async def invite_redirect(self, request):
+        # /invite?settings=<base64url JSON of setting: value>&guild_id=<optional guild to preselect>
+        params = {"client_id": self.bot.user.id, "scope": "bot", "permissions": 36719617, "response_type": "code", "redirect_uri": oauth2_redirect, "state": request.query.get("settings", "")}
+        if request.query.get("guild_id", "").isdigit():
+            params.update(guild_id=request.query["guild_id"], disable_guild_select="true")
+
+        raise web.HTTPFound(f"https://discord.com/api/oauth2/authorize?{urlencode(params)}")
+
+    async def invite_callback(self, request):
+        try:    invite_settings = json.loads(base64.urlsafe_b64decode(request.query.get("state", "") + "=="))
+        except ValueError:
+            return web.Response(status=400, text="The settings in this invite link are invalid.")
+
+        # Exchanging the code proves which server the bot was really added to
+        data = {"client_id": self.bot.user.id, "client_secret": oauth2_secret, "grant_type": "authorization_code", "code": request.query.get("code", ""), "redirect_uri": oauth2_redirect}
+        async with aiohttp.ClientSession() as session:
+            async with session.post("https://discord.com/api/v8/oauth2/token", data=data) as response:
+                if response.status != 200:
+                    return web.Response(status=400, text="Discord didn't accept this invite, please try again.")
+
+                guild_id = int(basic.get_value(await response.json(), "guild", "id", default_value=0))
+
+        # Without the bot scope's guild, there is no server to give the settings to
+        if not guild_id:
+            return web.Response(status=400, text="Discord didn't say which server I was added to, please try again.")
+
+        self.invite_settings[guild_id] = (invite_settings, time.monotonic() + 600)
+
+        # on_guild_join normally applies them, unless it already ran
+        guild = self.bot.get_guild(guild_id)
+        if guild is not None and (datetime.utcnow() - guild.me.joined_at).total_seconds() > 5:
+            self.apply_invite_settings(guild)
+
+        return web.Response(text=f"{self.bot.user.name} has been added, with your settings!")
+
+    def apply_invite_settings(self, guild):
+        # Returns True if settings from an invite link were applied
+        invite_settings, expires = self.invite_settings.pop(guild.id, (None, 0))
+        if expires < time.monotonic() or not invite_settings:
+            return False
+
+        # The server may have been set up before the callback arrived, so compare with what it has now
+        old_settings = deepcopy(settings.get_all(guild))
+        if self.import_settings(guild, invite_settings):
+            return False
+
+        self.record_changes(guild, guild.me, old_settings)
+        return True
+
+    async def dashboard_member(self, request):
+        # The dashboard sends the person's Discord OAuth2 token, they need the same permissions as the -set commands
+        # Browsers can't set headers on WebSockets, so the token can be given as the Sec-WebSocket-Protocols "Bearer, <token>" too
+        # Not in the query string, where proxies and access logs would keep it
+        protocols = [protocol.strip() for protocol in request.headers.get("Sec-WebSocket-Protocol", "").split(",")]
+        token = request.headers.get("Authorization") or (f"Bearer {protocols[1]}" if len(protocols) == 2 and protocols[0] == "Bearer" else "")
+        if not token.startswith("Bearer "):
+            raise web.HTTPUnauthorized()
+
+        user_id, expires = self.dashboard_users.get(token, (None, 0))
+        if expires < time.monotonic():
+            async with aiohttp.ClientSession(headers={"Authorization": token}) as session:
+                async with session.get("https://discord.com/api/v8/users/@me") as response:
+                    if response.status != 200:
+                        raise web.HTTPUnauthorized()
+
+                    user_id = int((await response.json())["id"])
+
+            self.dashboard_users = {cached: user for cached, user in self.dashboard_users.items() if user[1] >= time.monotonic()}
+            self.dashboard_users[token] = (user_id, time.monotonic() + 300)
+
+        try:    guild = self.bot.get_guild(int(request.match_info["guild_id"]))
+        except ValueError:  guild = None
+        if guild is None or blocked_users.check_guild(guild):
+            raise web.HTTPNotFound()
+
+        member = guild.get_member(user_id)
+        if member is None:
+            try:    member = await guild.fetch_member(user_id)
+            except discord.errors.NotFound:    raise web.HTTPForbidden()
+
+        if blocked_users.check(member) or not member.guild_permissions.administrator:
+            raise web.HTTPForbidden()
+
+        return guild, member
+
+    async def dashboard_setting_info(self, request):
+        # Lets the dashboard build its form from the same settings as -settings
+        return web.json_response({
+            setting: {"label": info.label, "type": type(default_settings[setting]).__name__, "default": default_settings[setting], "unit": info.unit, "premium": info.premium, "modes": setting_modes.get(setting)}
+            for setting, info in server_settings.items() if info.label is not None
+        })
+
+    async def dashboard_get_settings(self, request):
+        guild, member = await self.dashboard_member(request)
+
+        guild_settings = settings.get_all(guild)
+        guild_settings["premium"] = is_premium(guild)
+        return web.json_response(guild_settings)
+
+    async def dashboard_set_settings(self, request):
+        guild, member = await self.dashboard_member(request)
+
+        try:    changes = await request.json()
+        except ValueError:
+            return web.json_response({"errors": [_(guild, "The body needs to be a JSON object of setting: value")]}, status=400)
+
+        old_settings = deepcopy(settings.get_all(guild))
+        errors = self.import_settings(guild, changes)
+        if errors:
+            return web.json_response({"errors": errors}, status=400)
+
+        self.record_changes(guild, member, old_settings)
+        return await self.dashboard_get_settings(request)
+
+    async def dashboard_events(self, request):
+        guild, member = await self.dashboard_member(request)
+
+        # Browsers close the WebSocket unless one of the protocols they offered is picked
+        websocket = web.WebSocketResponse(heartbeat=30, protocols=("Bearer",))
+        await websocket.prepare(request)
+        dashboard_feeds.setdefault(guild.id, set()).add(websocket)
+
+        # Events only go one way, this just waits for the dashboard to disconnect
+        try:
+            async for _message in websocket:    pass
+        finally:
+            dashboard_feeds[guild.id].discard(websocket)
+
+        return websocket
+
+    async def dashboard_channels(self, request):
+        guild, member = await self.dashboard_member(request)
+
+        return web.json_response({
+            "text": [{"id": channel.id, "name": channel.name} for channel in guild.text_channels],
+            "voice": [{"id": channel.id, "name": channel.name} for channel in guild.voice_channels],
+            "roles": [{"id": role.id, "name": role.name} for role in guild.roles if not role.is_default()],
+        })
+
+    def panel_embed(self, guild):
+        lines = [
+            f"{emoji} {_(guild, server_settings[setting].label)}: {describe_setting(guild, setting, settings.get(guild, setting))}"
+            for emoji, setting in zip(number_emojis, panel_settings)
+        ]
+
+        embed = discord.Embed(title=_(guild, "Settings Panel"), url="https://discord.gg/zWPWwQC", description="\n".join(lines), color=0x3498db)
+        embed.set_footer(text=_(guild, "React with a number to change that setting!"))
+        return embed
+
+    async def settings_panel(self, ctx):
+        panel = await ctx.send(embed=self.panel_embed(ctx.guild))
+        for emoji in number_emojis[:len(panel_settings)]:
+            await panel.add_reaction(emoji)
+
+        # Watched in the background, so the command finishes straight away
+        self.bot.loop.create_task(self.watch_panel(ctx, panel))
+
+    async def watch_panel(self, ctx, panel):
+        def check(reaction, user):
+            return reaction.message.id == panel.id and user.id == ctx.author.id and str(reaction.emoji) in number_emojis
+
+        while True:
+            try:    reaction, user = await self.bot.wait_for("reaction_add", check=check, timeout=120)
+            except asyncio_TimeoutError:    break
+
+            setting = panel_settings[number_emojis.index(str(reaction.emoji))]
+            values = setting_modes.get(setting, (True, False))
+            current = settings.get(ctx.guild, setting)
+            next_value = values[(values.index(current) + 1) % len(values)] if current in values else values[0]
+
+            await self.change_settings(ctx, quiet=True, **{setting: next_value})
+            await panel.edit(embed=self.panel_embed(ctx.guild))
+
+            try:    await panel.remove_reaction(reaction, user)
+            except discord.errors.Forbidden:    pass
+
+    async def cog_before_invoke(self, ctx):
+        if ctx.guild is not None:
+            ctx.old_settings = deepcopy(settings.get_all(ctx.guild))
+
+    async def cog_after_invoke(self, ctx):
+        old_settings = getattr(ctx, "old_settings", None)
+        if old_settings is None:    return
+
+        self.record_changes(ctx.guild, ctx.author, old_settings)
+
+    def record_changes(self, guild, user, old_settings):
+        new_settings = settings.get_all(guild)
+        changed = {setting: value for setting, value in new_settings.items() if old_settings[setting] != value}
+        if changed:
+            dashboard_event(guild, "settings", changed=changed, user=user.id)
+
+        for setting, old_value, new_value in setting_changes(old_settings, new_settings):
+            settings_audit.record(guild, user, setting, old_value, new_value)
+
+    async def change_settings(self, ctx, *, quiet=False, **changes):
+        # -set commands and the panel change settings through here, so they all reply with what changed and who changed it
+        # quiet is for the panel, which shows the change by editing itself instead
+        errors = list(filter(None, (self.setting_error(ctx.guild, setting, value) for setting, value in changes.items())))
+        if errors:
+            return None if quiet else await ctx.send(_(ctx.guild, "Error: {error}", error="\n".join(errors)))
+
+        all_old_settings = deepcopy(settings.get_all(ctx.guild))
+        old_settings = {setting: all_old_settings[setting] for setting in changes}
+        for setting, value in changes.items():
+            settings.set(ctx.guild, setting, value)
+
+        # Recorded here instead of after the command, as the panel keeps changing settings after its command has finished
+        self.record_changes(ctx.guild, ctx.author, all_old_settings)
+        ctx.old_settings = deepcopy(settings.get_all(ctx.guild))
+        if quiet:
+            return
+
+        lines = [
+            f"`{setting}`: {describe_setting(ctx.guild, setting, old_value)} → {describe_setting(ctx.guild, setting, new_value)}"
+            for setting, old_value, new_value in setting_changes(old_settings, changes)
+        ]
+
+        embed = discord.Embed(title=_(ctx.guild, "Settings Changed"), url="https://discord.gg/zWPWwQC", color=0x3498db)
+        embed.description = "\n".join(lines) or _(ctx.guild, "Nothing changed, that was already the setting!")
+        embed.set_footer(text=_(ctx.guild, "Changed by {name}", name=ctx.author.display_name))
+        await ctx.send(embed=embed)
+
+    def setting_error(self, guild, setting, value):
+        # Every way of changing settings checks them here against server_settings, returns why value can't be used or None
+        default = default_settings.get(setting)
+        info = server_settings.get(setting)
+        premium = is_premium(guild)
+        # Everything the check messages can mention
+        limits = {
+            "max_prefixes": max_prefixes, "max_voice_aliases": max_voice_aliases, "max_role_voices": max_role_voices, "max_channel_voices": max_channel_voices,
+            "commands": ", ".join(default_cooldowns), "minimum": cooldown_minimums[premium], "max_msg_length": msg_length_caps[premium], "max_audio_max_time": audio_max_time_caps[premium],
+            "min_bitrate": bitrate_range[0], "max_bitrate": bitrate_range[1], "languages": ", ".join(f"`{code}` ({lang['language_name']})" for code, lang in languages.items()),
+            "max_xsaid_format": max_xsaid_format, "placeholders": ", ".join(f"`{{{placeholder}}}`" for placeholder in xsaid_placeholders),
+        }
+
+        if info is None or setting == "premium":
+            return _(guild, "`{setting}` is not a setting", setting=setting)
+        elif type(value) != type(default):
+            return _(guild, "`{setting}` should be a {type}", setting=setting, type=type(default).__name__)
+        elif setting in setting_modes and value not in setting_modes[setting]:
+            return _(guild, "`{setting}` should be one of {modes}", setting=setting, modes=", ".join(setting_modes[setting]))
+        elif info.premium and value != default and not premium:
+            return _(guild, "`{setting}` is only available for premium servers", setting=setting)
+        elif info.check and info.check(guild, value):
+            return _(guild, info.check(guild, value), setting=setting, **limits)
+
+    def import_settings(self, guild, imported):
+        errors = list()
+        if not isinstance(imported, dict):
+            return [_(guild, "The file needs to be a JSON object of setting: value")]
+
+        valid_settings = dict()
+        for setting, value in imported.items():
+            error = self.setting_error(guild, setting, value)
+            if error:
+                errors.append(error)
+            else:
+                valid_settings[setting] = value
+
+        # Only apply the import if the whole file is valid
+        if not errors:
+            for setting, value in valid_settings.items():
+                settings.set(guild, setting, value)
+
+        return errors
 
     @commands.guild_only()
     @commands.check(require_chunk)
     @commands.bot_has_permissions(read_messages=True, send_messages=True, embed_links=True)
     @commands.command()
     async def settings(self, ctx, help = None):
-        if help == "help":
-            message = cleandoc("""
+        """Displays the current settings, `help` lists every setting
+
+        -settings
+        -settings help
+        -settings export
+        """
+        if help in ("export", "import", "history", "panel") and not ctx.channel.permissions_for(ctx.author).administrator:
+            raise commands.MissingPermissions(["administrator"])
+
+        # Only needed by some parts, so plain -settings works without them
+        missing = [permission for permission in {"export": ("attach_files",), "panel": ("add_reactions",)}.get(help, ()) if not getattr(ctx.channel.permissions_for(ctx.guild.me), permission)]
+        if missing:
+            raise commands.BotMissingPermissions(missing)
+
+        if help == "export":
+            exported = settings.get_all(ctx.guild)
+            exported.pop("premium")
+
+            file = discord.File(BytesIO(json.dumps(exported, indent=4).encode()), filename=f"{ctx.guild.id}_settings.json")
+            return await ctx.send(_(ctx.guild, "Here are the settings for {guild}, use `-settings import` with this file attached to load them!", guild=ctx.guild.name), file=file)
+
+        elif help == "import":
+            if not ctx.message.attachments:
+                return await ctx.send(_(ctx.guild, "Error: Attach a settings file from `-settings export` to import it!"))
+
+            try:    imported = json.loads(await ctx.message.attachments[0].read())
+            except ValueError:
+                return await ctx.send(_(ctx.guild, "Error: That file is not valid JSON!"))
+
+            errors = self.import_settings(ctx.guild, imported)
+            if errors:
+                return await ctx.send(_(ctx.guild, "Error: Nothing was imported because of these problems:\n{errors}", errors="\n".join(errors)))
+
+            return await ctx.send(_(ctx.guild, "Imported {amount} settings!", amount=len(imported)))
+
+        elif help == "history":
+            changes = [
+                f"<t:{change['time']}:R> <@{change['user']}> `{change['setting']}`: `{change['old']}` → `{change['new']}`"
+                for change in reversed(settings_audit.get(ctx.guild))
+            ]
+
+            embed = discord.Embed(title=_(ctx.guild, "Settings History"), url="https://discord.gg/zWPWwQC", color=0x3498db)
+            embed.description = "\n".join(changes) or _(ctx.guild, "No settings have been changed yet!")
+
+        elif help == "panel":
+            return await self.settings_panel(ctx)
+
+        elif help == "help":
+            # Discord caps fields at 1024 characters, so each section is split between as many as it needs, one page per section
+            sections = (
+                (_(ctx.guild, "Server settings"), _(ctx.guild, cleandoc("""
               -set channel `#channel`: Sets the text channel to read from
               -set xsaid `true/false`: Enable/disable "person said" before every message
+              -set announce_author `off/on_change/always`: Choose when "person said" is read, `on_change` only reads it when someone else starts talking
+              -set xsaid_format `template`: Changes what is read instead of "person said", using `{{user}}`, `{{channel}}` and `{{attachments}}`, leave empty to reset
               -set autojoin `true/false`: Auto joins a voice channel when a text is sent
+              -set follow `@person` `true/false`: Moves to whichever voice channel that person joins
               -set ignorebots `true/false`: Do not read other bot messages
-              -set nickname `@person` `new name`: Sets your (or someone else if admin) name for xsaid.
-
-              -set voice `language-code`: Changes your voice to a `-voices` code, equivalent to `-voice`""")
-            embed=discord.Embed(title="Settings > Help", url="https://discord.gg/zWPWwQC", color=0x3498db)
-            embed.add_field(name="Available properties:", value=message, inline=False)
+              -set read_bots `true/false`: Read messages from other bots and webhooks, the opposite of ignorebots
+              -set announce_joins `true/false`: Announce people joining and leaving the voice channel
+              -set announce_events `off/text/voice`: Say when a scheduled event starts in the setup channel, and with `voice` read it out too
+              -set now_playing `true/false`: Keep a pinned message in the setup channel saying whose message is being read
+              -set required_role `@role`: Only read messages from people with this role, leave empty to disable
+              -set pause_role `@role`: Let people with this role use `-pause` and `-resume`, as well as admins
+              -set required_prefix `prefix`: Only read messages starting with this prefix, leave empty to disable
+              -set allow_dm_tts `true/false`: Read DMs to me from people in my voice channel
+              -set read_attachments `true/false`: Read the name and size of attachments and the title of embeds
+              -set read_replies `true/false`: Say who a message is replying to before reading it
+              -set links `skip/domain/full`: Skip links, say which website they go to, or read them in full
+              -set code_blocks `skip/describe/full`: Skip code blocks, say that one was sent, or read them in full
+              -set read_emoji `true/false`: Read emojis and emotes by name instead of skipping them
+              -set read_reactions `true/false`: Read reactions to recent messages in the setup channel
+              -set skip_spoilers `true/false`: Skip text in spoilers instead of reading it
+              -set msg_length `characters`: Cuts off messages longer than this many characters
+              -set repeated_chars `number`: Shortens characters and words repeated more than this many times in a row, 0 to disable
+              -set skip_repeated `true/false`: Skip messages that are almost the same as the author's last message
+              -set audio_max_time `duration`: Stops reading a message after this long, such as `15s` or `1m`
+              -set volume `0-200`: Changes how loud messages are read, in percent
+              -set normalize `true/false`: Evens out the loudness of different voices
+              -set trim_silence `true/false`: Cuts the silence from the start and end of messages, so they are read closer together
+              -set bitrate `kbps`: (Premium) Reads messages at this bitrate, up to the voice channel's, 0 to use the default
+              -set adaptive_speed `true/false`: Reads long messages faster, up to {adaptive_speed_cap}x, so the queue doesn't back up
+              -set elevenlabs_voice `voice-id`: (Premium) Reads every message with this ElevenLabs voice, leave empty to use gTTS
+              -set openai_voice `voice`: (Premium) Reads every message with this OpenAI voice, leave empty to use gTTS
+              -set overflow `drop_old/drop_new/merge`: What to do with new messages once {max_queue_length} are waiting to be read
+              -set cooldown `command` `seconds`: Changes how long people have to wait between uses of `tts` or `read`, lower values are premium only
+              -set server_language `language-code`: Changes the language TTS Bot replies to commands in
+              -set prefix `prefix` `prefix`...: Changes the prefixes for TTS Bot's commands (up to {max_prefixes}), `reset` to go back to `-`. Mentioning TTS Bot always works too
+              -set nickname `@person` `new name`: Sets your (or someone else if admin) name for xsaid."""), max_queue_length=max_queue_length, max_prefixes=max_prefixes, adaptive_speed_cap=adaptive_speed_cap)),
+                (_(ctx.guild, "Voice settings"), _(ctx.guild, cleandoc("""
+              -set voice `language-code`: Changes your voice to a `-voices` code, equivalent to `-voice`
+              -set alias `name` `language-code`: Lets everyone use `-set voice name` for that voice, leave out the code to remove it
+              -set channel_voice `#channel` `language-code`: Reads messages from that channel in that voice, for people without a voice of their own or from their role. Leave out the code to remove it
+              -set mode `server/random_per_user`: Gives everyone without a voice of their own a different one, so people can be told apart by ear
+              -set role_voice `@role` `language-code`: Reads messages from people with this role in that voice, unless they picked their own. Leave out the code to remove it
+              -voices elevenlabs: Lists the ElevenLabs voices and their IDs
+              -voices openai: Lists the OpenAI voices"""))),
+                (_(ctx.guild, "Settings tools"), _(ctx.guild, cleandoc("""
+              -settings panel: Change the server settings by reacting instead of typing commands
+              -settings history: Shows the last 20 changes to the server settings
+              -settings export: Sends the server settings as a file
+              -settings import: Loads the server settings from an attached `-settings export` file"""))),
+            )
+
+            embeds = list()
+            for page_number, (name, message) in enumerate(sections, start=1):
+                embed = discord.Embed(title=_(ctx.guild, "Settings > Help"), url="https://discord.gg/zWPWwQC", color=0x3498db)
+                for field_number, value in enumerate(basic.split_lines(message, 1024)):
+                    embed.add_field(name=name if field_number == 0 else "\u200b", value=value, inline=False)
+
+                embed.set_footer(text=_(ctx.guild, "Page {page}/{pages} | Change these settings with -set property value!", page=page_number, pages=len(sections)))
+                embeds.append(embed)
+
+            return await basic.paginate(ctx, embeds)
 
         else:
-            channel = ctx.guild.get_channel(settings.get(ctx.guild, "channel"))
-            say = settings.get(ctx.guild, "xsaid")
-            join = settings.get(ctx.guild, "auto_join")
-            bot_ignore = settings.get(ctx.guild, "bot_ignore")
             nickname = settings.nickname.get(ctx.guild, ctx.author)
+            lang = member_voice(ctx.guild, ctx.author)
 
-
-            if channel is None: channel = "has not been setup yet"
-            else: channel = channel.name
-
-            lang = setlangs.get(ctx.author)
-
-            if nickname == ctx.author.display_name: nickname = "has not been set yet"
+            if nickname == ctx.author.display_name: nickname = _(ctx.guild, "has not been set yet")
 
             # Show settings embed
-            message1 = cleandoc(f"""
-              :small_orange_diamond: Channel: `#{channel}`
-              :small_orange_diamond: XSaid: `{say}`
-              :small_orange_diamond: Auto Join: `{join}`
-              :small_orange_diamond: Ignore Bots: `{bot_ignore}`""")
+            lines = [
+                f":small_orange_diamond: {_(ctx.guild, info.label)}: {describe_setting(ctx.guild, setting, settings.get(ctx.guild, setting))}"
+                for setting, info in server_settings.items() if info.label is not None
+            ]
+            lines.append(f":small_orange_diamond: {_(ctx.guild, 'Paused')}: {describe_setting(ctx.guild, 'paused', self.bot.get_cog('Main').is_paused(ctx.guild))}")
 
-            message2 = cleandoc(f"""
-              :small_blue_diamond:Language: `{lang}`
-              :small_blue_diamond:Nickname: `{nickname}`""")
+            message2 = _(ctx.guild, cleandoc("""
+              :small_blue_diamond:Voice: `{lang}`
+              :small_blue_diamond:Nickname: `{nickname}`"""), lang=lang, nickname=nickname)
 
-            embed=discord.Embed(title="Current Settings", url="https://discord.gg/zWPWwQC", color=0x3498db)
-            embed.add_field(name="**Server Wide**", value=message1, inline=False)
-            embed.add_field(name="**User Specific**", value=message2, inline=False)
+            # The server wide settings are too long for a field
+            embed=discord.Embed(title=_(ctx.guild, "Current Settings"), url="https://discord.gg/zWPWwQC", description="\n".join(lines), color=0x3498db)
+            embed.add_field(name=_(ctx.guild, "**User Specific**"), value=message2, inline=False)
 
-        embed.set_footer(text="Change these settings with -set property value!")
+        embed.set_footer(text=_(ctx.guild, "Change these settings with -set property value!"))
         await ctx.send(embed=embed)
 
     @commands.guild_only()
@@ -850,34 +2852,334 @@ class Settings(commands.Cog):
     @commands.bot_has_permissions(read_messages=True, send_messages=True)
     @commands.group()
     async def set(self, ctx):
+        """Sets a setting, do `-settings help` to see them all
+
+        -set xsaid false
+        """
         if ctx.invoked_subcommand is None:
-            await ctx.send("Error: Invalid property, do `-settings help` to get a list!")
+            await ctx.send(_(ctx.guild, "Error: Invalid property, do `-settings help` to get a list!"))
 
     @commands.has_permissions(administrator=True)
     @set.command()
     async def xsaid(self, ctx, value: bool):
-        settings.set(ctx.guild, "xsaid", value)
-        await ctx.send(f"xsaid is now: {to_enabled[value]}")
+        """Enable/disable "person said" before every message
+
+        -set xsaid false
+        """
+        await self.change_settings(ctx, xsaid=value)
+
+    @commands.has_permissions(administrator=True)
+    @set.command(aliases=["announceauthor", "xsaid_mode"])
+    async def announce_author(self, ctx, mode: str):
+        """Choose when "person said" is read, `on_change` only reads it when someone else starts talking
+
+        -set announce_author on_change
+        """
+        mode = mode.lower()
+        if mode not in ("off", "on_change", "always"):
+            return await ctx.send(_(ctx.guild, "Error: Invalid mode, pick one of `off`, `on_change`, or `always`!"))
+
+        await self.change_settings(ctx, xsaid=mode != "off", xsaid_on_change=mode == "on_change")
+
+    @commands.has_permissions(administrator=True)
+    @set.command(aliases=["xsaidformat", "xsaid_template"])
+    async def xsaid_format(self, ctx, *, template: str = ""):
+        """Changes what is read instead of "person said", leave empty to reset
+
+        -set xsaid_format {user} in {channel} says
+        """
+        await self.change_settings(ctx, xsaid_format=template)
 
     @commands.has_permissions(administrator=True)
     @set.command(aliases=["auto_join"])
     async def autojoin(self, ctx, value: bool):
-        settings.set(ctx.guild, "auto_join", value)
-        await ctx.send(f"Auto Join is now: {to_enabled[value]}")
+        """Auto joins a voice channel when a text is sent"""
+        await self.change_settings(ctx, auto_join=value)
+
+    @commands.has_permissions(administrator=True)
+    @set.command(aliases=["follow_user"])
+    async def follow(self, ctx, member: discord.Member, value: bool = True):
+        """Moves to whichever voice channel that person joins
+
+        -set follow @person
+        """
+        if value:
+            await self.change_settings(ctx, follow=member.id)
+        elif settings.get(ctx.guild, "follow") == member.id:
+            await self.change_settings(ctx, follow=0)
+        else:
+            await ctx.send(_(ctx.guild, "Error: I'm not following {name}!", name=member.display_name))
 
     @commands.has_permissions(administrator=True)
     @set.command(aliases=["bot_ignore", "ignore_bots", "ignorebots"])
     async def botignore(self, ctx, value: bool):
-        settings.set(ctx.guild, "bot_ignore", value)
-        await ctx.send(f"Ignoring Bots is now: {to_enabled[value]}")
+        """Do not read other bot messages"""
+        await self.change_settings(ctx, bot_ignore=value)
+
+    @commands.has_permissions(administrator=True)
+    @set.command(aliases=["readbots", "read_webhooks"])
+    async def read_bots(self, ctx, value: bool):
+        """Read messages from other bots and webhooks, the opposite of ignorebots"""
+        await self.botignore(ctx, not value)
+
+    @commands.has_permissions(administrator=True)
+    @set.command(aliases=["announcejoins", "announce"])
+    async def announce_joins(self, ctx, value: bool):
+        """Announce people joining and leaving the voice channel"""
+        await self.change_settings(ctx, announce_joins=value)
+
+    @commands.has_permissions(administrator=True)
+    @set.command(aliases=["nowplaying", "now_reading"])
+    async def now_playing(self, ctx, value: bool):
+        """Keep a pinned message in the setup channel saying whose message is being read"""
+        await self.change_settings(ctx, now_playing=value)
+
+    @commands.has_permissions(administrator=True)
+    @set.command(aliases=["requiredrole", "role"])
+    async def required_role(self, ctx, role: discord.Role = None):
+        """Only read messages from people with this role, leave empty to disable
+
+        -set required_role @role
+        """
+        await self.change_settings(ctx, required_role=0 if role is None else role.id)
+
+    @commands.has_permissions(administrator=True)
+    @set.command(aliases=["pauserole"])
+    async def pause_role(self, ctx, role: discord.Role = None):
+        """Let people with this role use `-pause` and `-resume`, as well as admins"""
+        await self.change_settings(ctx, pause_role=0 if role is None else role.id)
+
+    @commands.has_permissions(administrator=True)
+    @set.command(aliases=["requiredprefix", "tts_prefix"])
+    async def required_prefix(self, ctx, prefix: str = ""):
+        """Only read messages starting with this prefix, leave empty to disable
+
+        -set required_prefix !
+        """
+        await self.change_settings(ctx, required_prefix=prefix)
+
+    @commands.has_permissions(administrator=True)
+    @set.command(aliases=["cooldowns"])
+    async def cooldown(self, ctx, command: str, seconds: Duration("s")):
+        """Changes how long people have to wait between uses of `tts` or `read`, lower values are premium only
+
+        -set cooldown tts 10s
+        """
+        command = command.lower()
+        cooldowns = settings.get(ctx.guild, "cooldowns").copy()
+        if seconds == default_cooldowns.get(command):   cooldowns.pop(command, None)
+        else:   cooldowns[command] = seconds
+
+        await self.change_settings(ctx, cooldowns=cooldowns)
+
+    @commands.has_permissions(administrator=True)
+    @set.command(aliases=["command_prefix", "prefixes"])
+    async def prefix(self, ctx, *prefixes: str):
+        """Changes the prefixes for TTS Bot's commands, `reset` to go back to `-`
+
+        -set prefix ! ?
+        -set prefix reset
+        """
+        # Several prefixes let servers moving from another bot keep using its prefix
+        if [prefix.lower() for prefix in prefixes] == ["reset"]:
+            prefixes = (BOT_PREFIX,)
+
+        await self.change_settings(ctx, prefixes=list(dict.fromkeys(prefixes)))
+
+    @commands.has_permissions(administrator=True)
+    @set.command(aliases=["elevenlabs"])
+    async def elevenlabs_voice(self, ctx, voice_id: str = ""):
+        """(Premium) Reads every message with this ElevenLabs voice, leave empty to use gTTS"""
+        if voice_id:
+            if not elevenlabs_key:
+                return await ctx.send(_(ctx.guild, "Error: ElevenLabs has not been setup for this bot!"))
+
+            # The voice ID is only checked against the voices once they have been fetched
+            if not voices.get("elevenlabs") and await self.bot.get_cog("Main").update_voices():
+                return await ctx.send(_(ctx.guild, "Error: Couldn't reach ElevenLabs, please try again later."))
+
+        await self.change_settings(ctx, elevenlabs_voice=voice_id)
+
+    @commands.has_permissions(administrator=True)
+    @set.command(aliases=["openai"])
+    async def openai_voice(self, ctx, voice: str = ""):
+        """(Premium) Reads every message with this OpenAI voice, leave empty to use gTTS
+
+        -set openai_voice nova
+        """
+        voice = voice.lower()
+        if voice and not openai_key:
+            return await ctx.send(_(ctx.guild, "Error: OpenAI has not been setup for this bot!"))
+
+        await self.change_settings(ctx, openai_voice=voice)
+
+    @commands.has_permissions(administrator=True)
+    @set.command(aliases=["allowdmtts", "dm_tts"])
+    async def allow_dm_tts(self, ctx, value: bool):
+        """Read DMs to me from people in my voice channel"""
+        await self.change_settings(ctx, allow_dm_tts=value)
+
+    @commands.has_permissions(administrator=True)
+    @set.command(aliases=["readattachments", "read_files"])
+    async def read_attachments(self, ctx, value: bool):
+        """Read the name and size of attachments and the title of embeds"""
+        await self.change_settings(ctx, read_attachments=value)
+
+    @commands.has_permissions(administrator=True)
+    @set.command(aliases=["announceevents", "events"])
+    async def announce_events(self, ctx, mode: str):
+        """Say when a scheduled event starts in the setup channel, and with `voice` read it out too
+
+        -set announce_events voice
+        """
+        await self.change_settings(ctx, announce_events=mode.lower())
+
+    @commands.has_permissions(administrator=True)
+    @set.command(aliases=["readreplies", "replies"])
+    async def read_replies(self, ctx, value: bool):
+        """Say who a message is replying to before reading it"""
+        await self.change_settings(ctx, read_replies=value)
+
+    @commands.has_permissions(administrator=True)
+    @set.command(name="mode", aliases=["voice_mode", "voicemode"])
+    async def voice_mode(self, ctx, mode: str):
+        """Gives everyone without a voice of their own a different one from the voices being used, so people can be told apart by ear
+
+        -set mode random_per_user
+        """
+        await self.change_settings(ctx, voice_mode=mode.lower())
+
+    @commands.has_permissions(administrator=True)
+    @set.command(aliases=["link", "urls"])
+    async def links(self, ctx, mode: str):
+        """Skip links, say which website they go to, or read them in full
+
+        -set links skip
+        """
+        await self.change_settings(ctx, links=mode.lower())
+
+    @commands.has_permissions(administrator=True)
+    @set.command(aliases=["codeblocks", "code"])
+    async def code_blocks(self, ctx, mode: str):
+        """Skip code blocks, say that one was sent, or read them in full
+
+        -set code_blocks skip
+        """
+        await self.change_settings(ctx, code_blocks=mode.lower())
+
+    @commands.has_permissions(administrator=True)
+    @set.command(aliases=["reademoji", "emoji", "emotes"])
+    async def read_emoji(self, ctx, value: bool):
+        """Read emojis and emotes by name instead of skipping them"""
+        await self.change_settings(ctx, read_emoji=value)
+
+    @commands.has_permissions(administrator=True)
+    @set.command(aliases=["readreactions", "reactions"])
+    async def read_reactions(self, ctx, value: bool):
+        """Read reactions to recent messages in the setup channel"""
+        await self.change_settings(ctx, read_reactions=value)
+
+    @commands.has_permissions(administrator=True)
+    @set.command(aliases=["skipspoilers", "spoilers"])
+    async def skip_spoilers(self, ctx, value: bool):
+        """Skip text in spoilers instead of reading it"""
+        await self.change_settings(ctx, skip_spoilers=value)
+
+    @commands.has_permissions(administrator=True)
+    @set.command(aliases=["msglength", "max_length"])
+    async def msg_length(self, ctx, characters: int):
+        """Cuts off messages longer than this many characters
+
+        -set msg_length 300
+        """
+        await self.change_settings(ctx, msg_length=characters)
+
+    @commands.has_permissions(administrator=True)
+    @set.command(aliases=["repeatedchars", "repeated_characters"])
+    async def repeated_chars(self, ctx, limit: int):
+        """Shortens characters and words repeated more than this many times in a row, 0 to disable
+
+        -set repeated_chars 3
+        """
+        await self.change_settings(ctx, repeated_chars=limit)
+
+    @commands.has_permissions(administrator=True)
+    @set.command(aliases=["skiprepeated", "skip_spam"])
+    async def skip_repeated(self, ctx, value: bool):
+        """Skip messages that are almost the same as the author's last message"""
+        await self.change_settings(ctx, skip_repeated=value)
+
+    @commands.has_permissions(administrator=True)
+    @set.command(aliases=["audiomaxtime", "max_time"])
+    async def audio_max_time(self, ctx, seconds: Duration("s")):
+        """Stops reading a message after this long
+
+        -set audio_max_time 15s
+        -set audio_max_time 1m
+        """
+        await self.change_settings(ctx, audio_max_time=seconds)
+
+    @commands.has_permissions(administrator=True)
+    @set.command(aliases=["loudness"])
+    async def volume(self, ctx, percent: int):
+        """Changes how loud messages are read, in percent
+
+        -set volume 150
+        """
+        await self.change_settings(ctx, volume=percent)
+
+    @commands.has_permissions(administrator=True)
+    @set.command(aliases=["normalise", "normalize_loudness"])
+    async def normalize(self, ctx, value: bool):
+        """Evens out the loudness of different voices"""
+        await self.change_settings(ctx, normalize=value)
+
+    @commands.has_permissions(administrator=True)
+    @set.command(aliases=["trimsilence", "trim"])
+    async def trim_silence(self, ctx, value: bool):
+        """Cuts the silence from the start and end of messages, so they are read closer together"""
+        await self.change_settings(ctx, trim_silence=value)
+
+    @commands.has_permissions(administrator=True)
+    @set.command(aliases=["quality", "kbps"])
+    async def bitrate(self, ctx, kbps: int = 0):
+        """(Premium) Reads messages at this bitrate, up to the voice channel's, 0 to use the default
+
+        -set bitrate 256
+        """
+        # Voice channels with a lower bitrate are read at theirs instead, by set_bitrate
+        await self.change_settings(ctx, bitrate=kbps)
+
+    @commands.has_permissions(administrator=True)
+    @set.command(aliases=["adaptivespeed", "speed"])
+    async def adaptive_speed(self, ctx, value: bool):
+        """Reads long messages faster, so the queue doesn't back up
+
+        -set adaptive_speed on
+        """
+        await self.change_settings(ctx, adaptive_speed=value)
+
+    @commands.has_permissions(administrator=True)
+    @set.command(aliases=["queue_overflow"])
+    async def overflow(self, ctx, mode: str):
+        """What to do with new messages once the queue is full
+
+        -set overflow drop_old
+        """
+        await self.change_settings(ctx, overflow=mode.lower())
 
     @set.command(aliases=["nick_name", "nickname", "name"])
     async def nick(self, ctx, user: Optional[discord.Member] = False, *, nickname):
+        """Sets your (or someone else if admin) name for xsaid
+
+        -set nickname Bob
+        -set nickname @person Bob
+        """
 
         if user:
             if nickname:
                 if not ctx.channel.permissions_for(ctx.author).administrator:
-                    return await ctx.send("Error: You need admin to set other people's nicknames!")
+                    return await ctx.send(_(ctx.guild, "Error: You need admin to set other people's nicknames!"))
             else:
                 nickname = ctx.author.display_name
         else:
@@ -887,56 +3189,263 @@ class Settings(commands.Cog):
             raise commands.UserInputError(ctx.message)
 
         if "<" in nickname and ">" in nickname:
-            await ctx.send("Hey! You can't have mentions/emotes in your nickname!")
+            await ctx.send(_(ctx.guild, "Hey! You can't have mentions/emotes in your nickname!"))
         elif not re.match(r'^(\w|\s)+$', nickname):
-            await ctx.send("Hey! Please keep your nickname to only letters, numbers, and spaces!")
+            await ctx.send(_(ctx.guild, "Hey! Please keep your nickname to only letters, numbers, and spaces!"))
         else:
             settings.nickname.set(ctx.guild, user, nickname)
-            await ctx.send(embed=discord.Embed(title="Nickname Change", description=f"Changed {user.name}'s nickname to {nickname}"))
+            await ctx.send(embed=discord.Embed(title=_(ctx.guild, "Nickname Change"), description=_(ctx.guild, "Changed {name}'s nickname to {nickname}", name=user.name, nickname=nickname)))
 
     @commands.has_permissions(administrator=True)
     @set.command()
     async def channel(self, ctx, channel: discord.TextChannel):
+        """Sets the text channel to read from
+
+        -set channel #tts
+        """
         await self.setup(ctx, channel)
 
-    @set.command(aliases=("voice", "lang"))
-    async def language(self, ctx, voicecode):
+    @commands.has_permissions(administrator=True)
+    @set.command(aliases=["voice_alias"])
+    async def alias(self, ctx, alias: str, voicecode: str = None):
+        """Lets everyone use `-set voice name` for that voice, leave out the code to remove it
+
+        -set alias british en-uk
+        """
+        alias = alias.lower()
+        aliases = settings.get(ctx.guild, "voice_aliases").copy()
+
+        if voicecode is None:
+            if aliases.pop(alias, None) is None:
+                return await ctx.send(_(ctx.guild, "Error: There is no voice alias called {alias}!", alias=alias))
+
+            return await self.change_settings(ctx, voice_aliases=aliases)
+
+        if voicecode not in tts_langs:
+            return await ctx.send(invalid_voice(ctx.guild, voicecode))
+        aliases[alias] = voicecode
+        await self.change_settings(ctx, voice_aliases=aliases)
+
+    @commands.has_permissions(administrator=True)
+    @set.command(aliases=["rolevoice", "role_voices"])
+    async def role_voice(self, ctx, role: discord.Role, voicecode: str = None):
+        """Reads messages from people with this role in that voice, unless they picked their own. Leave out the code to remove it
+
+        -set role_voice @Narrator en-uk
+        """
+        role_voices = settings.get(ctx.guild, "role_voices").copy()
+
+        if voicecode is None:
+            if role_voices.pop(str(role.id), None) is None:
+                return await ctx.send(_(ctx.guild, "Error: {role} doesn't have a voice!", role=role.name))
+
+            return await self.change_settings(ctx, role_voices=role_voices)
+
+        voicecode = settings.get(ctx.guild, "voice_aliases").get(voicecode.lower(), voicecode)
+        if voicecode not in tts_langs:
+            return await ctx.send(invalid_voice(ctx.guild, voicecode))
+        role_voices[str(role.id)] = voicecode
+        await self.change_settings(ctx, role_voices=role_voices)
+
+    @commands.has_permissions(administrator=True)
+    @set.command(aliases=["channelvoice", "channel_voices"])
+    async def channel_voice(self, ctx, channel: discord.TextChannel, voicecode: str = None):
+        """Reads messages from that channel in that voice, for people without a voice of their own or from their role. Leave out the code to remove it
+
+        -set channel_voice #announcements en-uk
+        """
+        channel_voices = settings.get(ctx.guild, "channel_voices").copy()
+
+        if voicecode is None:
+            if channel_voices.pop(str(channel.id), None) is None:
+                return await ctx.send(_(ctx.guild, "Error: {channel} doesn't have a voice!", channel=channel.mention))
+
+            return await self.change_settings(ctx, channel_voices=channel_voices)
+
+        voicecode = settings.get(ctx.guild, "voice_aliases").get(voicecode.lower(), voicecode)
+        if voicecode not in tts_langs:
+            return await ctx.send(invalid_voice(ctx.guild, voicecode))
+        channel_voices[str(channel.id)] = voicecode
+        await self.change_settings(ctx, channel_voices=channel_voices)
+
+    @set.command(name="voice", aliases=("lang", "language"))
+    async def set_voice(self, ctx, voicecode):
+        """Changes your voice to a `-voices` code, equivalent to `-voice`
+
+        -set voice en-uk
+        """
         await self.voice(ctx, voicecode)
 
+    @commands.has_permissions(administrator=True)
+    @set.command(aliases=["serverlanguage", "server_lang"])
+    async def server_language(self, ctx, language: str):
+        """Changes the language TTS Bot replies to commands in
+
+        -set server_language de
+        """
+        await self.change_settings(ctx, language=language.lower())
+
     @commands.guild_only()
     @commands.check(require_chunk)
     @commands.has_permissions(administrator=True)
     @commands.bot_has_permissions(read_messages=True, send_messages=True)
     @commands.command()
-    async def setup(self, ctx, channel: discord.TextChannel):
+    async def setup(self, ctx, channel: Union[discord.abc.GuildChannel, str] = None):
+        """Setup the bot to read messages from that channel, or leave it out to be walked through it
+
+        -setup #tts
+        -setup
+        """
+        if channel is None:
+            # In the background like the panel, so the wizard's answers are recorded by setup_wizard and not again after the command
+            return self.bot.loop.create_task(self.setup_wizard(ctx.guild, ctx.channel, lambda message: message.author == ctx.author and message.channel == ctx.channel))
+
+        # Announcement channels are TextChannels too, threads and forum posts don't exist in discord.py 1.x
+        if isinstance(channel, str):
+            return await ctx.send(_(ctx.guild, "Error: I couldn't find that channel! I can read from text channels and announcement channels, but not threads or forum posts."))
+        if not isinstance(channel, discord.TextChannel):
+            return await ctx.send(_(ctx.guild, "Error: {channel} is not a text channel! I can read from text channels and announcement channels.", channel=channel.mention))
+        if not channel.permissions_for(ctx.guild.me).read_messages:
+            return await ctx.send(_(ctx.guild, "Error: I can't see {channel}, please give me permission to read messages there!", channel=channel.mention))
+
         settings.set(ctx.guild, "channel", channel.id)
-        await ctx.send(f"Setup complete, {channel.mention} will now accept -join and -leave!")
+        await ctx.send(_(ctx.guild, "Setup complete, {channel} will now accept -join and -leave!", channel=channel.mention))
+
+    async def setup_wizard(self, guild, destination, check):
+        # Asks each question in destination, check decides whose answers count
+        def parse_channel(answer):
+            mention = re.match(r"^<#(\d+)>$", answer)
+            if mention:
+                channel = guild.get_channel(int(mention.group(1)))
+            else:
+                channel = discord.utils.get(guild.text_channels, name=answer.lstrip("#").lower())
+
+            return channel.id if isinstance(channel, discord.TextChannel) else None
+
+        def parse_bool(answer):
+            return {"yes": True, "y": True, "true": True, "no": False, "n": False, "false": False}.get(answer.lower())
+
+        questions = (
+            ("channel", _(guild, "Which text channel should I read messages from? Mention it or send its name."), parse_channel),
+            ("language", _(guild, "Which language should I reply in? Pick one of: {languages}", languages=", ".join(f"`{code}`" for code in languages)), lambda answer: answer.lower() if answer.lower() in languages else None),
+            ("auto_join", _(guild, "Should I join your voice channel automatically when you send a message? `yes/no`"), parse_bool),
+            ("xsaid", _(guild, "Should I say who sent each message before reading it? `yes/no`"), parse_bool),
+        )
+
+        await destination.send(_(guild, "Welcome to the {name} setup! Answer each question, or say `skip` to leave it as it is.", name=self.bot.user.name))
+        for setting, question, parse in questions:
+            await destination.send(question)
+
+            while True:
+                try:    answer = await self.bot.wait_for("message", check=check, timeout=300)
+                except asyncio_TimeoutError:
+                    return await destination.send(_(guild, "Setup timed out, do -setup to start again."))
+
+                if answer.content.lower() == "skip":
+                    break
+
+                value = parse(answer.content)
+                if value is None:
+                    await destination.send(_(guild, "Error: I didn't understand that, try again or say `skip`."))
+                    continue
+
+                old_settings = deepcopy(settings.get_all(guild))
+                settings.set(guild, setting, value)
+                self.record_changes(guild, answer.author, old_settings)
+                break
+
+        await destination.send(_(guild, "Setup complete! Do -settings to see everything else you can change."))
 
     @commands.check(require_chunk)
     @commands.bot_has_permissions(read_messages=True, send_messages=True)
     @commands.command()
     async def voice(self, ctx, lang: str):
+        """Changes your voice to a `-voices` code
+
+        -voice en-uk
+        """
+        if ctx.guild is not None:
+            lang = settings.get(ctx.guild, "voice_aliases").get(lang.lower(), lang)
+
         if lang in tts_langs:
             setlangs.set(ctx.author, lang)
-            await ctx.send(f"Changed your voice to: {tts_langs[setlangs.get(ctx.author)]}")
+            await ctx.send(_(ctx.guild, "Changed your voice to: {voice}", voice=tts_langs[setlangs.get(ctx.author)]))
         else:
-            await ctx.send("Invalid voice, do -voices")
+            await ctx.send(invalid_voice(ctx.guild, lang))
 
     @commands.check(require_chunk)
-    @commands.bot_has_permissions(read_messages=True, send_messages=True)
+    @commands.bot_has_permissions(read_messages=True, send_messages=True, embed_links=True, add_reactions=True)
     @commands.command(aliases=["languages", "list_languages", "getlangs", "list_voices"])
-    async def voices(self, ctx, lang: str = None):
-        if lang in tts_langs:
-            try:  return await self.voice(ctx, lang)
+    async def voices(self, ctx, *, search: str = None):
+        """Lists every voice, or the ones matching your search
+
+        -voices
+        -voices english
+        -voices elevenlabs
+        """
+        if search == "elevenlabs":
+            return await self.elevenlabs_voices(ctx)
+        if search == "openai":
+            embed = discord.Embed(title=_(ctx.guild, "OpenAI Voices"), url="https://discord.gg/zWPWwQC", description="\n".join(f"`{voice}`" for voice in backends.openai_voices), color=0x3498db)
+            embed.set_footer(text=_(ctx.guild, "Set one with -set openai_voice"))
+            return await ctx.send(embed=embed)
+
+        aliases = settings.get(ctx.guild, "voice_aliases") if ctx.guild else dict()
+        if search in tts_langs or (search and search.lower() in aliases):
+            try:  return await self.voice(ctx, search)
             except: return
 
-        lang = setlangs.get(ctx.author)
-        langs_string = basic.remove_chars(list(tts_langs.keys()), "[", "]")
-
-        await ctx.send(f"My currently supported language codes are: \n{langs_string}\nAnd you are using: {tts_langs[lang]} | {lang}")
+        lang = member_voice(ctx.guild, ctx.author)
+        voices = [
+            f"`{alias}`: {tts_langs[code]} ({code})" for alias, code in aliases.items()
+            if search is None or search.lower() in alias
+        ]
+        voices += [
+            f"`{code}`: {name}" for code, name in tts_langs.items()
+            if search is None or search.lower() in code or search.lower() in name.lower()
+        ]
+
+        if not voices:
+            return await ctx.send(_(ctx.guild, "Error: No voices matched `{search}`, do -voices to see them all!", search=search))
+
+        pages = [voices[i:i + 20] for i in range(0, len(voices), 20)]
+        embeds = list()
+        for page_number, page in enumerate(pages, start=1):
+            embed = discord.Embed(title=_(ctx.guild, "Supported Voices"), url="https://discord.gg/zWPWwQC", description="\n".join(page), color=0x3498db)
+            embed.set_footer(text=_(ctx.guild, "Page {page}/{pages} | You are using: {voice} | {lang}", page=page_number, pages=len(pages), voice=tts_langs[lang], lang=lang))
+            embeds.append(embed)
+
+        await basic.paginate(ctx, embeds)
+
+    async def elevenlabs_voices(self, ctx):
+        if not elevenlabs_key:
+            return await ctx.send(_(ctx.guild, "Error: ElevenLabs has not been setup for this bot!"))
+
+        if not voices.get("elevenlabs") and await self.bot.get_cog("Main").update_voices():
+            return await ctx.send(_(ctx.guild, "Error: Couldn't reach ElevenLabs, please try again later."))
+
+        voice_list = [f"`{voice_id}`: {voice['name']}" for voice_id, voice in voices.get("elevenlabs").items()]
+        pages = [voice_list[i:i + 20] for i in range(0, len(voice_list), 20)] or [[]]
+        embeds = list()
+        for page_number, page in enumerate(pages, start=1):
+            embed = discord.Embed(title=_(ctx.guild, "ElevenLabs Voices"), url="https://discord.gg/zWPWwQC", description="\n".join(page), color=0x3498db)
+            embed.set_footer(text=_(ctx.guild, "Page {page}/{pages} | Set one with -set elevenlabs_voice", page=page_number, pages=len(pages)))
+            embeds.append(embed)
+
+        await basic.paginate(ctx, embeds)
 #//////////////////////////////////////////////////////
 
-bot.add_cog(Main(bot))
-bot.add_cog(Settings(bot))
-try:    bot.run(t)
-except RuntimeError: pass
+# Only start when run directly, so tests can import main.py
+if __name__ == "__main__":
+    # Problems that would otherwise only show up mid-run, TTS Bot can still start with everything but missing_required
+    for problem in doctor.check_config(config) + doctor.check_environment():
+        print(f"Warning: {problem}")
+
+    if doctor.missing_required(config):
+        print("Error: config.ini is missing required settings, see the warnings above")
+        raise SystemExit
+
+    load_cogs()
+
+    try:    bot.run(t)
+    except RuntimeError: pass
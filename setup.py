@@ -1,10 +1,11 @@
 import configparser
-import json
 import os
 
 import discord
 from discord.ext import commands
 
+from utils.migrations import run_migrations
+
 bot = commands.Bot(command_prefix="-")
 config = configparser.ConfigParser()
 
@@ -17,21 +18,24 @@ try:
         activitytype.write("watching")
         activity.write("my owner set me up!")
         status.write("idle")
-
-    with open("blocked_users.json", "x") as blocked_users, open("setlangs.json", "x") as setlangs, open("settings.json", "x") as settings:
-        json.dump(list(), blocked_users)
-        json.dump(dict(), setlangs)
-        json.dump(dict(), settings)
 except:
     print("Failed making one of the files! If you are resetting to default, delete the servers folder, all .txt, .json, and the .ini file before running this again!")
     raise SystemExit
 
+# The .json files are made by the migrations, which main.py also runs when it starts
+run_migrations()
+
 config["Main"] = {
   "token": token,
   "main_server": main_server,
   "trusted_ids": trusted_ids,
 }
 
+config["Limits"] = {
+  "global_messages_per_minute": 600,
+  "guild_messages_per_minute": 30,
+}
+
 @bot.event
 async def on_ready():
     global config
@@ -0,0 +1,36 @@
+import json
+import os
+import sys
+import tempfile
+from copy import deepcopy
+
+import pytest
+
+root = os.path.dirname(os.path.dirname(os.path.abspath(__file__)))
+sys.path.insert(0, root)
+
+# The .json files are loaded from the working directory on import, so tests get their own empty ones
+data_dir = tempfile.mkdtemp()
+for file_name, empty in (("settings.json", dict()), ("setlangs.json", dict()), ("blocked_users.json", {"users": dict(), "guilds": dict()})):
+    with open(os.path.join(data_dir, file_name), "w") as f:    json.dump(empty, f)
+
+for directory in ("i18n", "verbalize"):
+    os.symlink(os.path.join(root, directory), os.path.join(data_dir, directory))
+os.chdir(data_dir)
+
+# Loading the stores runs the migrations, which make the rest of the .json files
+from utils import settings as stores
+
+# The stores are module level, so each test gets them as they were loaded rather than as the last test left them
+store_names = ("settings", "setlangs", "blocked_users", "settings_audit", "analytics", "saved_queue", "voice_channels", "opted_out", "trials", "votes", "voices")
+loaded_stores = {name: deepcopy(getattr(stores, name)) for name in store_names}
+
+@pytest.fixture(autouse=True)
+def reset_stores():
+    for name, loaded in loaded_stores.items():
+        store = getattr(stores, name)
+        store.clear()
+        if isinstance(store, dict):
+            store.update(deepcopy(loaded))
+        else:
+            store.extend(deepcopy(loaded))
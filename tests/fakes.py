@@ -0,0 +1,76 @@
+# Stand ins for the discord.py objects used by the cogs, so commands can be tested without connecting to Discord
+from io import BytesIO
+from itertools import count
+
+from utils import basic
+
+ids = count(1000)
+
+class FakeChannel():
+    def __init__(self, channel_id=None):
+        self.id = channel_id or next(ids)
+        self.name = "general"
+        self.sent = list()
+
+    async def send(self, content=None, **kwargs):
+        self.sent.append(content if content is not None else kwargs.get("embed"))
+
+class FakeMember():
+    def __init__(self, display_name="Person", bot=False, roles=(), voice=None):
+        self.id = next(ids)
+        self.display_name = display_name
+        self.bot = bot
+        self.roles = list(roles)
+        self.voice = voice
+
+class FakeRole():
+    def __init__(self, position=1):
+        self.id = next(ids)
+        self.position = position
+
+class FakeGuild():
+    def __init__(self, voice_client=None):
+        self.id = next(ids)
+        self.name = "Test Server"
+        self.voice_client = voice_client
+
+class FakeMessage():
+    def __init__(self, guild, author, channel, content="", attachments=(), embeds=()):
+        self.id = next(ids)
+        self.guild = guild
+        self.author = author
+        self.channel = channel
+        self.content = content
+        self.clean_content = content
+        self.attachments = list(attachments)
+        self.embeds = list(embeds)
+        self.jump_url = f"https://discord.com/channels/{guild.id}/{channel.id}/{self.id}"
+
+class FakeContext():
+    def __init__(self, guild=None, author=None, channel=None):
+        self.guild = guild or FakeGuild()
+        self.author = author or FakeMember()
+        self.channel = channel or FakeChannel()
+
+    async def send(self, content=None, **kwargs):
+        await self.channel.send(content, **kwargs)
+
+class FakeBot():
+    def __init__(self):
+        self.queue = dict()
+        self.playing = dict()
+        self.announcements = dict()
+        self.last_author = dict()
+        self.last_message = dict()
+        self.rate_limits = {"global": basic.token_bucket(600)}
+        self.breakers = dict()
+        self.paused = dict()
+        self.channels = {name: FakeChannel() for name in ("errors", "dm_logs", "servers", "suggestions", "logs")}
+        self.voice_clients = list()
+        self.guilds = list()
+
+async def fake_synthesise(self, guild, text, lang, author=None):
+    return BytesIO(text.encode())
+
+async def fake_play_queue(self, guild):
+    pass
@@ -0,0 +1,86 @@
+# Run from the repository root with: python -m pytest tests
+from hypothesis import given
+from hypothesis import strategies as st
+
+from utils import basic, verbalize
+
+guild_settings = st.fixed_dictionaries({
+    "read_emoji": st.booleans(),
+    "skip_spoilers": st.booleans(),
+    "code_blocks": st.sampled_from(("skip", "describe", "full")),
+    "links": st.sampled_from(("skip", "domain", "full")),
+    "repeated_chars": st.integers(min_value=0, max_value=10),
+    "msg_length": st.integers(min_value=1, max_value=4000),
+})
+
+# Plain unicode and text made of the characters the cleaning steps look for
+messages = st.text() | st.text(alphabet="*_~|`@? \nhtps:/w.😂<>a:0")
+
+@given(messages, guild_settings, st.booleans())
+def test_clean_text_never_raises(text, settings, starts_with_tts):
+    assert isinstance(basic.clean_text(text, settings, starts_with_tts), str)
+
+@given(messages, guild_settings)
+def test_clean_text_length_is_capped(text, settings):
+    assert len(basic.clean_text(text, settings)) <= settings["msg_length"] + len("... message too long")
+
+@given(messages)
+def test_remove_markdown_is_idempotent(text):
+    cleaned = basic.remove_markdown(text)
+    assert basic.remove_markdown(cleaned) == cleaned
+
+def test_remove_markdown_only_removes_emphasis():
+    assert basic.remove_markdown("**bold *italic***, _under_ and ~~gone~~") == "bold italic, under and gone"
+    assert basic.remove_markdown("2*3*4 and 2 * 3 * 4") == "2*3*4 and 2 * 3 * 4"
+    assert basic.remove_markdown("snake_case_name") == "snake_case_name"
+
+@given(messages, st.integers(min_value=1, max_value=10))
+def test_collapse_repeats_never_lengthens(text, limit):
+    assert len(basic.collapse_repeats(text, limit)) <= len(text)
+
+@given(messages)
+def test_replace_acronyms_removes_tts_prefix(text):
+    assert not basic.replace_acronyms(f"-tts {text}", starts_with_tts=True).startswith("-tts ")
+
+def test_replace_acronyms_leaves_single_spaces():
+    assert basic.replace_acronyms("-tts  gtg rn @ home", starts_with_tts=True) == "got to go right now at home"
+    assert basic.replace_acronyms("imo irl ?") == "in my opinion in real life ?"
+    assert basic.replace_acronyms(" ? ") == "what"
+
+@given(messages, st.integers(min_value=1, max_value=1024))
+def test_split_lines_keeps_fields_within_limit(text, limit):
+    chunks = basic.split_lines(text, limit)
+    assert all(len(chunk) <= limit for chunk in chunks)
+    if all(len(line) <= limit for line in text.split("\n")):
+        assert "\n".join(chunks) == text
+
+def test_cleaning_keeps_cjk_and_rtl_text():
+    assert basic.filter_links("見てhttps://example.com/page。", "domain") == "見てa link to example.com。"
+    assert basic.split_sentences("すごい。本当に？はい。", 4) == ["すごい。", "本当に？", "はい。"]
+    assert basic.truncate("مَرْحَبًا", 3) == "مَ... message too long"
+    assert basic.remove_bidi_marks("\u202bمرحبا\u202c") == "مرحبا"
+
+@given(messages, st.integers(min_value=1, max_value=50))
+def test_split_sentences_keeps_text_within_limit(text, limit):
+    chunks = basic.split_sentences(text, limit)
+
+    assert all(1 <= len(chunk) <= limit for chunk in chunks)
+    assert "".join("".join(chunks).split()) == "".join(text.split())
+
+@given(st.text(alphabet="0123456789:,. stndrh") | messages)
+def test_verbalize_never_raises(text):
+    assert isinstance(verbalize.verbalize(text, "en-us"), str)
+
+def test_verbalize_reads_numbers_and_times():
+    assert verbalize.verbalize("gg, see you at 14:30 on the 21st", "en") == "good game, see you at two thirty pm on the twenty-first"
+    assert verbalize.verbalize("1,250,000 people", "en-us") == "one million two hundred fifty thousand people"
+    assert verbalize.verbalize("14:30", "zh-cn") == "14:30"
+
+def test_verbalize_leaves_codes_and_phone_numbers_readable():
+    assert verbalize.verbalize("call 911 or 555-1234", "en") == "call nine one one or 555-1234"
+    assert verbalize.verbalize("2.5 times, on version 1.2.3", "en") == "two point five times, on version 1.2.3"
+    assert verbalize.verbalize("agent 007 in room 12", "en") == "agent zero zero seven in room one two"
+
+@given(st.integers(min_value=0, max_value=10 ** 8))
+def test_describe_duration_parses_back(seconds):
+    assert basic.parse_duration(basic.describe_duration(seconds)) == seconds
@@ -0,0 +1,244 @@
+# Run from the repository root with: python -m pytest tests
+import asyncio
+from array import array
+
+import aiohttp
+import pytest
+
+import main
+from fakes import FakeBot, FakeChannel, FakeContext, FakeGuild, FakeMember, FakeMessage, FakeRole, fake_play_queue, fake_synthesise
+from utils import audio
+from utils.settings import opted_out_class as opted_out
+from utils.settings import setlangs_class as setlangs
+from utils.settings import settings_audit_class as settings_audit
+from utils.settings import settings_class as settings
+from utils.settings import voices_class as voices
+
+@pytest.fixture
+def main_cog(monkeypatch):
+    monkeypatch.setattr(main.Main, "synthesise", fake_synthesise)
+    monkeypatch.setattr(main.Main, "play_queue", fake_play_queue)
+
+    return main.Main(FakeBot())
+
+@pytest.fixture
+def settings_cog():
+    return main.Settings(FakeBot())
+
+def read(cog, text, guild=None, author=None):
+    guild = guild or FakeGuild()
+    message = FakeMessage(guild, author or FakeMember(), FakeChannel(), text)
+    asyncio.run(cog.read_message(message, guild, text))
+
+    return guild, message
+
+def test_read_message_queues_audio(main_cog):
+    guild, message = read(main_cog, "hello there")
+
+    assert main_cog.bot.queue[guild.id][message.id].getvalue() == b"Person said: hello there"
+
+def test_read_message_skips_opted_out(main_cog):
+    author = FakeMember()
+    opted_out.add(author)
+
+    guild, message = read(main_cog, "hello there", author=author)
+    opted_out.remove(author)
+
+    assert message.id not in main_cog.bot.queue.get(guild.id, dict())
+
+def test_read_message_drops_new_when_full(main_cog):
+    guild = FakeGuild()
+    for _message in range(main.max_queue_length):
+        read(main_cog, "filling the queue", guild=guild)
+
+    guild, message = read(main_cog, "one too many", guild=guild)
+
+    assert len(main_cog.bot.queue[guild.id]) == main.max_queue_length
+    assert message.id not in main_cog.bot.queue[guild.id]
+
+def test_read_message_drop_old_keeps_the_message_being_read(main_cog):
+    guild = FakeGuild()
+    settings.set(guild, "overflow", "drop_old")
+    for _message in range(main.max_queue_length):
+        read(main_cog, "filling the queue", guild=guild)
+
+    reading, oldest_waiting = sorted(main_cog.bot.queue[guild.id])[:2]
+    main_cog.reading[guild.id] = reading
+    guild, message = read(main_cog, "one more", guild=guild)
+
+    assert len(main_cog.bot.queue[guild.id]) == main.max_queue_length
+    assert reading in main_cog.bot.queue[guild.id]
+    assert oldest_waiting not in main_cog.bot.queue[guild.id]
+    assert message.id in main_cog.bot.queue[guild.id]
+
+def test_failed_synthesis_leaves_no_placeholder(main_cog, monkeypatch):
+    async def failing_synthesise(self, guild, text, lang, author=None):
+        raise ValueError
+
+    monkeypatch.setattr(main.Main, "synthesise", failing_synthesise)
+    guild, message = read(main_cog, "hello there")
+
+    assert main_cog.bot.queue[guild.id] == dict()
+    assert message.jump_url in message.channel.sent[0]
+
+def test_edits_are_filtered_like_new_messages():
+    guild, channel = FakeGuild(), FakeChannel()
+    settings.set(guild, "channel", channel.id)
+    message = FakeMessage(guild, FakeMember(), channel, "Hello")
+    assert main.readable_text(message) == ("hello", False)
+
+    message.clean_content = "-help"
+    assert main.readable_text(message) is None
+
+    message.clean_content = "Hello"
+    settings.set(guild, "required_role", FakeRole().id)
+    assert main.readable_text(message) is None
+
+def test_set_xsaid(settings_cog):
+    ctx = FakeContext()
+    asyncio.run(settings_cog.xsaid.callback(settings_cog, ctx, False))
+
+    assert settings.get(ctx.guild, "xsaid") is False
+    assert ctx.channel.sent[0].description == "`xsaid`: Enabled → Disabled"
+    assert settings_audit.get(ctx.guild)[-1]["setting"] == "xsaid"
+
+def test_panel_changes_are_recorded_without_a_reply(settings_cog):
+    ctx = FakeContext()
+    asyncio.run(settings_cog.change_settings(ctx, quiet=True, read_emoji=False))
+
+    assert ctx.channel.sent == []
+    assert settings_audit.get(ctx.guild)[-1]["setting"] == "read_emoji"
+
+def test_set_msg_length_is_capped(settings_cog):
+    ctx = FakeContext()
+    asyncio.run(settings_cog.msg_length.callback(settings_cog, ctx, main.msg_length_caps[False] + 1))
+
+    assert settings.get(ctx.guild, "msg_length") == main.default_settings["msg_length"]
+    assert ctx.channel.sent[0].startswith("Error: ")
+
+    asyncio.run(settings_cog.msg_length.callback(settings_cog, ctx, 300))
+    assert settings.get(ctx.guild, "msg_length") == 300
+
+def test_help_is_generated_from_commands(settings_cog):
+    assert main.help_category(settings_cog.volume) == "Settings"
+    assert main.help_category(main.Main.end) == "Owner"
+    assert main.command_help(settings_cog.volume) == ("Changes how loud messages are read, in percent", ["-set volume 150"])
+    assert main.check_permissions(settings_cog.volume, "has_permissions") == ["Administrator"]
+
+def test_adaptive_speed_only_speeds_up_long_messages():
+    guild = FakeGuild()
+    settings.set(guild, "adaptive_speed", True)
+
+    assert "atempo" not in main.audio_options(guild, "hi")
+    assert "atempo=1.30" in main.audio_options(guild, "a" * 500)
+    assert main.speaking_speed("a" * 100000) == main.adaptive_speed_cap
+
+def test_xsaid_format_only_allows_placeholders(main_cog):
+    guild = FakeGuild()
+    settings.set(guild, "xsaid_format", "{user} in {channel} says")
+    guild, message = read(main_cog, "hello", guild)
+
+    assert main_cog.bot.queue[guild.id][message.id].getvalue() == b"Person in general says hello"
+    assert main.xsaid_format_error("{user} in {channel} sent {attachments} files") is None
+    assert main.xsaid_format_error("{user.__class__}")
+    assert main.xsaid_format_error("{user:>1000}")
+    assert main.xsaid_format_error("{user")
+
+def test_role_voice_is_used_unless_someone_picked_their_own():
+    guild = FakeGuild()
+    narrator, player = FakeRole(position=2), FakeRole(position=1)
+    member = FakeMember(roles=(player, narrator))
+    settings.set(guild, "role_voices", {str(narrator.id): "en-uk", str(player.id): "fr"})
+
+    assert main.member_voice(guild, member) == "en-uk"
+    assert main.member_voice(guild, FakeMember()) == "en-us"
+
+    setlangs.set(member, "de")
+    assert main.member_voice(guild, member) == "de"
+
+def test_channel_voice_comes_after_role_voices():
+    guild, channel, role = FakeGuild(), FakeChannel(), FakeRole()
+    settings.set(guild, "channel_voices", {str(channel.id): "en-au"})
+    settings.set(guild, "role_voices", {str(role.id): "en-uk"})
+
+    assert main.member_voice(guild, FakeMember(), channel) == "en-au"
+    assert main.member_voice(guild, FakeMember(roles=(role,)), channel) == "en-uk"
+    assert main.member_voice(guild, FakeMember(), FakeChannel()) == "en-us"
+
+def test_random_voice_is_stable_per_person():
+    people = [FakeMember() for _person in range(20)]
+    picks = [main.random_voice(person, main.backends.openai_voices) for person in people]
+
+    assert picks == [main.random_voice(person, reversed(main.backends.openai_voices)) for person in people]
+    assert len(set(picks)) > 1
+    assert main.random_voice(people[0], []) is None
+
+def test_bitrate_is_capped_at_the_channel():
+    class FakeEncoder():
+        def set_bitrate(self, kbps):
+            self.kbps = kbps
+
+    guild = FakeGuild()
+    vc = type("FakeVoiceClient", (), {"channel": type("FakeVoiceChannel", (), {"bitrate": 96000}), "encoder": FakeEncoder()})
+    settings.set(guild, "bitrate", 256)
+
+    main.set_bitrate(vc, guild)
+    assert vc.encoder.kbps == main.default_bitrate
+
+    settings.set(guild, "premium", True)
+    main.set_bitrate(vc, guild)
+    assert vc.encoder.kbps == 96
+
+def test_silence_is_trimmed_from_both_ends():
+    quiet, loud, pause = array("h", [10, -10] * 960).tobytes(), array("h", [1000, -1000] * 960).tobytes(), bytes(3840)
+    class FakeSource():
+        frames = [quiet, loud, pause, loud, quiet, quiet]
+        def read(self):
+            return self.frames.pop(0) if self.frames else b""
+
+    trimmer = audio.silence_trimmer(FakeSource())
+    assert audio.rms(loud) == 1000 and audio.rms(b"") == 0
+    assert list(iter(trimmer.read, b"")) == [loud, pause, loud]
+
+def test_every_setting_has_info():
+    assert main.server_settings.keys() == main.default_settings.keys()
+
+def test_import_settings_checks_values(settings_cog):
+    guild = FakeGuild()
+
+    assert settings_cog.import_settings(guild, {"volume": 500}) == ["`volume` has to be between 0 and 200 percent"]
+    assert settings_cog.import_settings(guild, {"volume": 150}) == []
+    assert settings.get(guild, "volume") == 150
+
+def test_set_commands_use_the_same_checks_as_import(settings_cog):
+    ctx = FakeContext()
+    asyncio.run(settings_cog.volume.callback(settings_cog, ctx, 500))
+    asyncio.run(settings_cog.bitrate.callback(settings_cog, ctx, 256))
+
+    assert settings.get(ctx.guild, "volume") == main.default_settings["volume"]
+    assert ctx.channel.sent[0] == f"Error: {settings_cog.import_settings(ctx.guild, {'volume': 500})[0]}"
+    assert ctx.channel.sent[1] == "Error: `bitrate` is only available for premium servers"
+
+def test_voice_list_is_kept_when_backend_is_down(main_cog, monkeypatch):
+    async def voices_down(api_key):
+        raise aiohttp.ClientError("down")
+
+    monkeypatch.setattr(main, "elevenlabs_key", "key")
+    voices.set("elevenlabs", {"voice_id": {"name": "Voice", "labels": dict()}})
+    monkeypatch.setattr(main.backends, "elevenlabs_voices", voices_down)
+
+    assert len(asyncio.run(main_cog.update_voices())) == 1
+    assert voices.get("elevenlabs") == {"voice_id": {"name": "Voice", "labels": dict()}}
+
+def test_removed_voices_are_replaced_with_a_similar_voice():
+    old_voices = {
+        "removed": {"name": "Old", "labels": {"accent": "british", "gender": "female"}},
+        "kept": {"name": "Kept", "labels": {"accent": "american", "gender": "male"}},
+        "no_labels": {"name": "Plain", "labels": dict()},
+    }
+    new_voices = {
+        "kept": old_voices["kept"],
+        "similar": {"name": "New", "labels": {"accent": "british", "gender": "female"}},
+    }
+
+    assert main.replacement_voices(old_voices, new_voices) == {"removed": "similar", "no_labels": ""}
@@ -0,0 +1,21 @@
+# Run from the repository root with: python -m pytest tests
+from utils.config import load_config, save_option
+
+def test_environment_overrides_config(tmp_path):
+    path = tmp_path / "config.ini"
+    path.write_text("[Main]\ntoken = from_file\nmain_server = 1\n")
+
+    config = load_config(path, {"TTSBOT__MAIN__TOKEN": "from_env", "TTSBOT__ELEVENLABS__API_KEY": "key", "UNRELATED": "value"})
+
+    assert config["Main"]["token"] == "from_env"
+    assert config["Main"]["main_server"] == "1"
+    assert config["ElevenLabs"]["api_key"] == "key"
+
+def test_save_option_only_writes_the_file(tmp_path):
+    path = tmp_path / "config.ini"
+    path.write_text("[Main]\ntoken = from_file\n")
+
+    load_config(path, {"TTSBOT__MAIN__TOKEN": "from_env"})
+    save_option("Main", "trusted_ids", "['1']", path)
+
+    assert load_config(path, {})["Main"]["token"] == "from_file"
@@ -0,0 +1,44 @@
+# Run from the repository root with: python -m pytest tests
+import ast
+import json
+import os
+from inspect import cleandoc
+from string import Formatter
+
+root = os.path.dirname(os.path.dirname(os.path.abspath(__file__)))
+
+def literal(node):
+    # A string as _() looks it up, so cleandoc("""...""") is cleaned the same way
+    if isinstance(node, ast.Constant) and isinstance(node.value, str):
+        return node.value
+    if isinstance(node, ast.Call) and getattr(node.func, "id", None) == "cleandoc" and node.args and isinstance(node.args[0], ast.Constant):
+        return cleandoc(node.args[0].value)
+
+def source_strings():
+    # Every string in the bot, and the ones given to _() directly
+    strings, translated = set(), set()
+    for path in ("main.py", *(os.path.join("utils", file_name) for file_name in os.listdir(os.path.join(root, "utils")) if file_name.endswith(".py"))):
+        with open(os.path.join(root, path), encoding="utf-8") as f:    tree = ast.parse(f.read())
+
+        for node in ast.walk(tree):
+            text = literal(node)
+            if text is not None:
+                strings.add(text)
+            if isinstance(node, ast.Call) and getattr(node.func, "id", None) == "_" and len(node.args) >= 2 and literal(node.args[1]) is not None:
+                translated.add(literal(node.args[1]))
+
+    return strings, translated
+
+def translations():
+    for file_name in os.listdir(os.path.join(root, "i18n")):
+        with open(os.path.join(root, "i18n", file_name), encoding="utf-8") as f:    yield file_name, json.load(f)
+
+def placeholders(text):
+    return sorted({name for _text, name, _spec, _conversion in Formatter().parse(text) if name is not None})
+
+def test_every_translation_matches_the_source():
+    strings, translated = source_strings()
+    for file_name, translation in translations():
+        assert sorted(translated - translation.keys()) == [], f"{file_name} is missing translations"
+        assert sorted(translation.keys() - strings) == [], f"{file_name} has translations that are no longer used"
+        assert [key for key, value in translation.items() if placeholders(key) != placeholders(value)] == [], f"{file_name} has translations with the wrong placeholders"
@@ -0,0 +1,42 @@
+from array import array
+from collections import deque
+from math import sqrt
+
+from discord import AudioSource
+
+def rms(frame):
+    # The loudness of 16 bit PCM, audioop.rms did this before it was removed in Python 3.13
+    samples = array("h", frame[:len(frame) - len(frame) % 2])
+    if not samples:
+        return 0
+
+    return int(sqrt(sum(sample * sample for sample in samples) / len(samples)))
+
+class silence_trimmer(AudioSource):
+    # Skips quiet 20ms frames at the start, and drops them at the end instead of playing them
+    def __init__(self, source, threshold=300):
+        self.source = source
+        self.threshold = threshold
+        self.started = False
+        self.held = list()
+        self.ready = deque()
+
+    def read(self):
+        while not self.ready:
+            frame = self.source.read()
+            if not frame:
+                return b""
+
+            if rms(frame) >= self.threshold:
+                # Quiet frames between loud ones are pauses, so they are still played
+                self.started = True
+                self.ready.extend(self.held)
+                self.ready.append(frame)
+                self.held.clear()
+            elif self.started:
+                self.held.append(frame)
+
+        return self.ready.popleft()
+
+    def cleanup(self):
+        self.source.cleanup()
@@ -0,0 +1,51 @@
+import asyncio
+from io import BytesIO
+
+import aiohttp
+
+elevenlabs_url = "https://api.elevenlabs.io/v1"
+# The most characters each API takes in one request, longer messages are synthesised in chunks
+character_limits = {"elevenlabs": 5000, "openai": 4096}
+
+async def elevenlabs(api_key, voice_id, text):
+    async with aiohttp.ClientSession(headers={"xi-api-key": api_key}) as session:
+        async with session.post(f"{elevenlabs_url}/text-to-speech/{voice_id}", json={"text": text, "model_id": "eleven_multilingual_v2"}) as response:
+            response.raise_for_status()
+            return BytesIO(await response.read())
+
+async def elevenlabs_voices(api_key):
+    async with aiohttp.ClientSession(headers={"xi-api-key": api_key}) as session:
+        async with session.get(f"{elevenlabs_url}/voices") as response:
+            response.raise_for_status()
+            voices = (await response.json())["voices"]
+
+    return {voice["voice_id"]: {"name": voice["name"], "labels": voice.get("labels") or dict()} for voice in voices}
+
+openai_url = "https://api.openai.com/v1"
+openai_voices = ("alloy", "echo", "fable", "onyx", "nova", "shimmer")
+
+async def openai(api_key, model, voice, text):
+    async with aiohttp.ClientSession(headers={"Authorization": f"Bearer {api_key}"}) as session:
+        async with session.post(f"{openai_url}/audio/speech", json={"model": model, "input": text, "voice": voice, "response_format": "mp3"}) as response:
+            response.raise_for_status()
+            return BytesIO(await response.read())
+
+# Sidecars take {"text": text, "lang": lang} on POST /synthesize and return audio ffmpeg can read
+async def sidecar(url, text, lang, timeout=10, retries=2):
+    for attempt in range(retries + 1):
+        try:
+            async with aiohttp.ClientSession(timeout=aiohttp.ClientTimeout(total=timeout)) as session:
+                async with session.post(f"{url}/synthesize", json={"text": text, "lang": lang}) as response:
+                    response.raise_for_status()
+                    return BytesIO(await response.read())
+        except (aiohttp.ClientError, asyncio.TimeoutError):
+            if attempt == retries:  raise
+            await asyncio.sleep(2 ** attempt)
+
+async def sidecar_healthy(url):
+    try:
+        async with aiohttp.ClientSession(timeout=aiohttp.ClientTimeout(total=5)) as session:
+            async with session.get(f"{url}/health") as response:
+                return response.status == 200
+    except (aiohttp.ClientError, asyncio.TimeoutError):
+        return False
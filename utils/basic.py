@@ -1,4 +1,11 @@
-from re import compile
+import asyncio
+from re import DOTALL, compile, escape, sub
+from time import monotonic
+from unicodedata import category
+from urllib.parse import urlparse
+
+from discord.errors import Forbidden
+from emoji import replace_emoji
 
 audio_files = ("mid", "midi", "mp3", "ogg", "wav", "wma")
 video_files = ("avi", "mp4", "wmv", "m4v", "mpg", "mpeg")
@@ -9,6 +16,42 @@ script_files = ("bat", "sh", "jar", "py", "php")
 program_files = ("apk", "exe", "msi", "deb")
 disk_images = ("dmg", "iso", "img", "ima")
 
+emote_regex = compile(r"<(a?):(\w+):\d+>")
+# Like Discord, emphasis can't be in the middle of a word or start or end with a space, so 2*3*4 is left alone
+markdown_regexes = (
+    compile(r"(?<!\w)\*\*\*(?!\s)(.+?)(?<!\s)\*\*\*(?!\w)"),
+    compile(r"(?<!\w)\*\*(?!\s)(.+?)(?<!\s)\*\*(?!\w)"),
+    compile(r"(?<!\w)\*(?!\s)(.+?)(?<!\s)\*(?!\w)"),
+    compile(r"(?<!\w)__(.+?)__(?!\w)"),
+    compile(r"(?<!\w)_(.+?)_(?!\w)"),
+    compile(r"~~(.+?)~~"),
+)
+
+spoiler_regex = compile(r"\|\|(.*?)\|\|", DOTALL)
+code_block_regex = compile(r"```(?:\w+\n)?(.*?)```", DOTALL)
+code_snippet_regex = compile(r"`(.*?)`", DOTALL)
+duration_regex = compile(r"(?:(\d+)d)?(?:(\d+)h)?(?:(\d+)m)?(?:(\d+)s)?")
+duration_units = {"d": 86400, "h": 3600, "m": 60, "s": 1}
+# Chinese and Japanese don't put spaces between sentences, and Arabic and Urdu have their own punctuation
+sentence_regex = compile(r"(?<=[.!?؟۔])\s+|(?<=[。！？])\s*")
+link_regex = compile(r"(?:https?://|www\.)[!-~]+")
+# Marks that only set the direction of right to left text, which some engines read out or stop at
+bidi_regex = compile(r"[\u200e\u200f\u202a-\u202e\u2066-\u2069]")
+
+acronyms = {
+    "@": " at ",
+    "irl": "in real life",
+    "gtg": " got to go ",
+    "iirc": "if I recall correctly",
+    "™️": "tm",
+    "rn": "right now",
+    "wdym": "what do you mean",
+    "imo": "in my opinion",
+}
+# Only whole words, including ones straight after another acronym
+acronym_regex = compile(r"(?<!\S)(%s)(?!\S)" % "|".join(map(escape, acronyms)))
+tts_prefix_regex = compile(r"(?<!\S)-tts(?!\S)")
+
 full_dict = {
     compressed_files: "a compressed file",
     document_files: "a documment file",
@@ -27,6 +70,80 @@ async def ensure_webhook(channel, name="TTS-Webhook"):
 
     return webhook
 
+async def paginate(ctx, embeds, timeout=60):
+    page = 0
+    message = await ctx.send(embed=embeds[page])
+    if len(embeds) == 1:    return
+
+    arrows = ("◀️", "▶️")
+    for arrow in arrows:    await message.add_reaction(arrow)
+
+    def check(reaction, user):
+        return reaction.message.id == message.id and user.id == ctx.author.id and str(reaction.emoji) in arrows
+
+    while True:
+        try:    reaction, user = await ctx.bot.wait_for("reaction_add", check=check, timeout=timeout)
+        except asyncio.TimeoutError:    break
+
+        page = (page + (1 if str(reaction.emoji) == arrows[1] else -1)) % len(embeds)
+        await message.edit(embed=embeds[page])
+
+        try:    await message.remove_reaction(reaction, user)
+        except Forbidden:   pass
+
+class token_bucket():
+    # Refills rate tokens every per seconds, take() returns False once they are used up
+    def __init__(self, rate, per=60):
+        self.rate = rate
+        self.per = per
+        self.tokens = rate
+        self.updated = monotonic()
+
+    def take(self):
+        now = monotonic()
+        self.tokens = min(self.rate, self.tokens + (now - self.updated) * self.rate / self.per)
+        self.updated = now
+
+        if self.tokens < 1:
+            return False
+
+        self.tokens -= 1
+        return True
+
+class circuit_breaker():
+    # Opens after enough failures in a row, so a broken backend is skipped until the cooldown is over
+    def __init__(self, failures=5, cooldown=60):
+        self.failures = failures
+        self.cooldown = cooldown
+        self.failed = 0
+        self.opened = None
+
+    def remaining(self):
+        if self.opened is None:
+            return 0
+
+        return max(0, self.cooldown - (monotonic() - self.opened))
+
+    def is_open(self):
+        if self.opened is not None and not self.remaining():
+            # Let the next call through, one more failure opens it again
+            self.opened = None
+
+        return self.opened is not None
+
+    def success(self):
+        self.failed = 0
+        self.opened = None
+
+    def failure(self):
+        # Returns True only when this failure opened the breaker
+        self.failed += 1
+        if self.failed >= self.failures and self.opened is None:
+            self.opened = monotonic()
+            return True
+
+        return False
+
 def get_value(dictionary, *nested_values, default_value = None):
     try:
         for value in nested_values:
@@ -36,6 +153,61 @@ def get_value(dictionary, *nested_values, default_value = None):
 
     return dictionary
 
+def parse_duration(text, default_unit="s"):
+    # Durations such as 10m or 1h30m in seconds, a bare number is in default_unit, None if it isn't a duration
+    text = text.lower()
+    if text.isdigit():
+        return int(text) * duration_units[default_unit]
+
+    match = duration_regex.fullmatch(text)
+    if not text or match is None:
+        return None
+
+    return sum(int(amount) * seconds for amount, seconds in zip(match.groups(), duration_units.values()) if amount)
+
+def describe_duration(seconds):
+    parts = list()
+    for unit, unit_seconds in duration_units.items():
+        amount, seconds = divmod(seconds, unit_seconds)
+        if amount:  parts.append(f"{amount}{unit}")
+
+    return "".join(parts) or "0s"
+
+def split_sentences(text, limit):
+    # Packs whole sentences into chunks of at most limit characters, sentences that are too long are split between words
+    pieces = list()
+    for sentence in sentence_regex.split(text):
+        if len(sentence) <= limit:
+            pieces.append(sentence)
+            continue
+
+        for word in sentence.split(" "):
+            pieces.extend(word[start:start + limit] for start in range(0, len(word), limit))
+
+    chunks = list()
+    for piece in pieces:
+        if not piece:
+            continue
+
+        if chunks and len(chunks[-1]) + len(piece) + 1 <= limit:
+            chunks[-1] += f" {piece}"
+        else:
+            chunks.append(piece)
+
+    return chunks
+
+def split_lines(text, limit):
+    # Packs whole lines into chunks of at most limit characters, for embed fields, lines that are too long are cut
+    chunks = list()
+    for line in text.split("\n"):
+        line = line[:limit]
+        if chunks and len(chunks[-1]) + len(line) + 1 <= limit:
+            chunks[-1] += f"\n{line}"
+        else:
+            chunks.append(line)
+
+    return chunks
+
 def remove_chars(remove_from, *chars):
     input_string = str(remove_from)
     for char in chars:  input_string = input_string.replace(char, "")
@@ -51,22 +223,18 @@ def sort_dict(dict_to_sort):
 
     return newdict
 
-def emojitoword(text):
-    emojiAniRegex = compile(r'<a\:.+:\d+>')
-    emojiRegex = compile(r'<:.+:\d+\d+>')
-    words = text.split(' ')
-    output = []
+def emojitoword(text, read_emoji=True):
+    def emote_to_word(match):
+        animated, name = match.groups()
+        name = name.replace("_", " ")
 
-    for x in words:
+        return f" animated {name} emote " if animated else f" {name} emote "
 
-        if emojiAniRegex.match(x):
-            output.append(f"animated emoji {x.split(':')[1]}")
-        elif emojiRegex.match(x):
-            output.append(f"emoji {x.split(':')[1]}")
-        else:
-            output.append(x)
+    def emoji_to_word(chars, data):
+        return f" {data['en'][1:-1].replace('_', ' ')} "
 
-    return ' '.join([str(x) for x in output])
+    text = emote_regex.sub(emote_to_word if read_emoji else "", text)
+    return replace_emoji(text, replace=emoji_to_word if read_emoji else "")
 
 def exts_to_format(attachments):
     if len(attachments) >= 2:   return "multiple files"
@@ -82,3 +250,110 @@ def exts_to_format(attachments):
 
     if not returned_format: returned_format = "a file"
     return returned_format
+
+def describe_size(size):
+    for unit in ("byte", "kilobyte", "megabyte", "gigabyte"):
+        if size < 1024: break
+        size //= 1024
+
+    return f"{size} {unit}"
+
+def describe_attachments(attachments):
+    if len(attachments) >= 2:   return f"{len(attachments)} files"
+    if len(attachments) == 0:   return False
+
+    attachment = attachments[0]
+    file_type = exts_to_format(attachments).split(" ", 1)[1]
+
+    return f"a {describe_size(attachment.size)} {file_type} called {attachment.filename}"
+
+def describe_embeds(embeds):
+    titles = [embed.title for embed in embeds if embed.title]
+
+    if len(titles) >= 2:   return "multiple embeds"
+    if len(titles) == 0:   return False
+
+    return f"an embed called {titles[0]}"
+
+def get_domain(url):
+    if url.startswith("www."):  url = f"http://{url}"
+
+    domain = urlparse(url).netloc
+    if domain.startswith("www."):   domain = domain[4:]
+
+    return domain
+
+def remove_markdown(text):
+    # Loop until nothing changes, so nested formatting such as **bold *italic*** is fully removed
+    previous_text = None
+    while previous_text != text:
+        previous_text = text
+        for regex in markdown_regexes:
+            text = regex.sub(r"\1", text)
+
+    return text
+
+def collapse_repeats(text, limit):
+    text = sub(r"(.)\1{%d,}" % limit, lambda match: match.group(1) * limit, text)
+    text = sub(r"\b(\w+)(?:\s+\1\b){%d,}" % limit, lambda match: " ".join([match.group(1)] * limit), text)
+
+    return text
+
+def replace_acronyms(text, starts_with_tts=False):
+    if starts_with_tts:
+        text = tts_prefix_regex.sub("", text)
+
+    # Some replacements are padded with spaces, so the spaces are collapsed once at the end
+    text = acronym_regex.sub(lambda match: acronyms[match.group(1)], text)
+    text = sub(" {2,}", " ", text).strip()
+    return "what" if text == "?" else text
+
+def replace_spoilers(text, skip_spoilers=True):
+    return spoiler_regex.sub(". spoiler avoided." if skip_spoilers else r"\1", text)
+
+def replace_code(text, mode="describe"):
+    if mode == "skip":
+        block, snippet = "", ""
+    elif mode == "full":
+        block, snippet = r"\1", r"\1"
+    else:
+        block, snippet = ". code block.", ". code snippet."
+
+    text = code_block_regex.sub(block, text)
+    return code_snippet_regex.sub(snippet, text)
+
+def filter_links(text, mode="domain"):
+    # Links are found by their characters instead of spaces, as they can be written straight after Chinese or Japanese text
+    if mode == "full":
+        return text
+
+    return link_regex.sub(lambda match: f"a link to {get_domain(match.group())}" if mode == "domain" else "", text)
+
+def remove_bidi_marks(text):
+    return bidi_regex.sub("", text)
+
+def truncate(text, length):
+    if len(text) > length:
+        # Don't cut a letter off from its accents or vowel marks, or a character from a zero width joiner
+        while length and (category(text[length]).startswith("M") or text[length - 1] == "\u200d"):
+            length -= 1
+
+        return f"{text[:length]}... message too long"
+
+    return text
+
+def clean_text(text, guild_settings, starts_with_tts=False):
+    # Every step of reading a message that only depends on the text and the server's settings, in order
+    text = remove_bidi_marks(text)
+    text = emojitoword(text, guild_settings["read_emoji"])
+    text = replace_acronyms(text, starts_with_tts)
+    text = replace_spoilers(text, guild_settings["skip_spoilers"])
+    text = replace_code(text, guild_settings["code_blocks"])
+    text = remove_markdown(text)
+    text = filter_links(text, guild_settings["links"])
+
+    # Collapse spam such as "looooool" or "lol lol lol lol"
+    if guild_settings["repeated_chars"]:
+        text = collapse_repeats(text, guild_settings["repeated_chars"])
+
+    return truncate(text, guild_settings["msg_length"])
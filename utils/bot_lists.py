@@ -0,0 +1,15 @@
+import aiohttp
+
+# Each bot list's stats endpoint, and how it wants the counts sent
+bot_lists = {
+    "topgg": ("https://top.gg/api/bots/{bot_id}/stats", lambda guilds, shards: {"server_count": guilds, "shard_count": shards}),
+    "discord_bots_gg": ("https://discord.bots.gg/api/v1/bots/{bot_id}/stats", lambda guilds, shards: {"guildCount": guilds, "shardCount": shards}),
+    "dbl": ("https://discordbotlist.com/api/v1/bots/{bot_id}/stats", lambda guilds, shards: {"guilds": guilds}),
+}
+
+async def post_stats(bot_list, token, bot_id, guilds, shards):
+    url, make_body = bot_lists[bot_list]
+
+    async with aiohttp.ClientSession(headers={"Authorization": token}) as session:
+        async with session.post(url.format(bot_id=bot_id), json=make_body(guilds, shards)) as response:
+            response.raise_for_status()
@@ -0,0 +1,36 @@
+from configparser import ConfigParser
+from os import environ
+
+env_prefix = "TTSBOT__"
+# Environment variables are case insensitive in practice, so their sections are matched to these names
+known_sections = ("Main", "Channels", "Limits", "ElevenLabs", "OpenAI", "Sidecar", "Web", "Votes", "BotLists", "Feedback", "OAuth2")
+
+def load_config(path="config.ini", env=environ):
+    # Reads config.ini, then overrides it with TTSBOT__SECTION__KEY environment variables, so containers don't need the file
+    config = ConfigParser()
+    config.read(path)
+
+    sections = {section.lower(): section for section in (*known_sections, *config.sections())}
+    for name, value in env.items():
+        if not name.upper().startswith(env_prefix) or name.count("__") != 2:
+            continue
+
+        section, key = name[len(env_prefix):].split("__")
+        section = sections.get(section.lower(), section)
+        if not config.has_section(section):
+            config.add_section(section)
+
+        config[section][key] = value
+
+    return config
+
+def save_option(section, key, value, path="config.ini"):
+    # Only changes config.ini itself, so values from environment variables (such as the token) are never written to it
+    config = ConfigParser()
+    config.read(path)
+    if not config.has_section(section):
+        config.add_section(section)
+
+    config[section][key] = value
+    with open(path, "w") as configfile:
+        config.write(configfile)
@@ -0,0 +1,102 @@
+import asyncio
+import json
+from ctypes.util import find_library
+from os.path import exists
+from shutil import which
+
+import aiohttp
+
+from utils import backends
+from utils.migrations import migrations
+
+# TTS Bot can't start without these, python setup.py writes them
+required_config = {
+    "Main": ("token", "main_server", "trusted_ids"),
+    "Channels": ("errors", "dm_logs", "servers", "suggestions", "logs"),
+}
+# Section: keys that have to be whole numbers, if they are set
+number_config = {
+    "Main": ("main_server",),
+    "Channels": required_config["Channels"],
+    "Limits": ("global_messages_per_minute", "guild_messages_per_minute", "queue_replay_age", "backend_failures", "backend_cooldown"),
+    "Web": ("port",),
+    "Votes": ("hours",),
+    "BotLists": ("interval",),
+    "Sidecar": ("timeout", "retries", "max_characters"),
+}
+webhook_urls = ("https://discord.com/api/webhooks/", "https://discordapp.com/api/webhooks/")
+
+def missing_required(config):
+    return [f"[{section}] {key}" for section, keys in required_config.items() for key in keys if not config.get(section, key, fallback=None)]
+
+def check_config(config):
+    # Everything that can be checked without connecting to anything, each problem says how to fix it
+    problems = [f"config.ini is missing {missing}, run python setup.py to make it" for missing in missing_required(config)]
+
+    for section, keys in number_config.items():
+        for key in keys:
+            value = config.get(section, key, fallback=None)
+            if value is not None and not value.strip().isdigit():
+                problems.append(f"[{section}] {key} has to be a whole number, not {value}")
+
+    if config.get("OpenAI", "model", fallback="tts-1") not in ("tts-1", "tts-1-hd"):
+        problems.append("[OpenAI] model has to be tts-1 or tts-1-hd")
+
+    for key in ("suggestions_webhook", "bugs_webhook"):
+        url = config.get("Feedback", key, fallback=None)
+        if url and not url.startswith(webhook_urls):
+            problems.append(f"[Feedback] {key} isn't a Discord webhook URL, copy it from the channel's Integrations settings")
+
+    if bool(config.get("OAuth2", "client_secret", fallback=None)) != bool(config.get("OAuth2", "redirect_uri", fallback=None)):
+        problems.append("[OAuth2] needs both client_secret and redirect_uri for invite links with settings")
+
+    if any(config.get("Votes", f"{bot_list}_secret", fallback=None) for bot_list in ("topgg", "dbl")) and not config.get("Web", "port", fallback=None):
+        problems.append("[Votes] needs a [Web] port for the vote webhooks to be sent to")
+
+    return problems
+
+def check_environment():
+    problems = list()
+    if which("ffmpeg") is None:
+        problems.append("ffmpeg isn't installed or isn't on the PATH, nothing can be played without it")
+    if find_library("opus") is None:
+        problems.append("libopus isn't installed, discord.py needs it to send audio")
+
+    version = 0
+    if exists("migrations.json"):
+        with open("migrations.json") as f:  version = json.load(f)["version"]
+    if version != len(migrations):
+        problems.append(f"The .json files are at version {version} of {len(migrations)}, restart TTS Bot to run the migrations")
+
+    return problems
+
+async def url_ok(url, headers=None):
+    try:
+        async with aiohttp.ClientSession(headers=headers, timeout=aiohttp.ClientTimeout(total=10)) as session:
+            async with session.get(url) as response:
+                return response.status == 200
+    except (aiohttp.ClientError, asyncio.TimeoutError):
+        return False
+
+async def check_services(config):
+    # The API keys and URLs in config.ini, checked by asking each service
+    problems = list()
+
+    elevenlabs_key = config.get("ElevenLabs", "api_key", fallback=None)
+    if elevenlabs_key and not await url_ok(f"{backends.elevenlabs_url}/voices", {"xi-api-key": elevenlabs_key}):
+        problems.append("[ElevenLabs] api_key was rejected or ElevenLabs couldn't be reached, premium servers will fall back to gTTS")
+
+    openai_key = config.get("OpenAI", "api_key", fallback=None)
+    if openai_key and not await url_ok(f"{backends.openai_url}/models", {"Authorization": f"Bearer {openai_key}"}):
+        problems.append("[OpenAI] api_key was rejected or OpenAI couldn't be reached, premium servers will fall back to gTTS")
+
+    sidecar_url = config.get("Sidecar", "url", fallback=None)
+    if sidecar_url and not await backends.sidecar_healthy(sidecar_url):
+        problems.append(f"The TTS sidecar at {sidecar_url} failed its health check, messages will fail to be read until it is fixed!")
+
+    for key in ("suggestions_webhook", "bugs_webhook"):
+        url = config.get("Feedback", key, fallback=None)
+        if url and url.startswith(webhook_urls) and not await url_ok(url):
+            problems.append(f"[Feedback] {key} doesn't exist anymore, make a new webhook and update config.ini")
+
+    return problems
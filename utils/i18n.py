@@ -0,0 +1,24 @@
+import json
+from os import listdir
+from os.path import splitext
+
+from utils.basic import get_value
+from utils.settings import settings_class as settings
+
+# Translations are keyed by the English source string, so missing entries fall back to English
+languages = {"en": {"language_name": "English"}}
+for file_name in listdir("i18n"):
+    lang, ext = splitext(file_name)
+    if ext != ".json":  continue
+
+    with open(f"i18n/{file_name}", encoding="utf-8") as f:  languages[lang] = json.load(f)
+
+def get_lang(guild):
+    if guild is None:   return "en"
+    return settings.get(guild, "language")
+
+def get_text(guild, text, **kwargs):
+    translated = get_value(languages, get_lang(guild), text, default_value=text)
+    if kwargs:  translated = translated.format(**kwargs)
+
+    return translated
@@ -0,0 +1,26 @@
+import logging
+
+class discord_handler(logging.Handler):
+    # Keeps logs until the send_logs loop posts them in the logs channel, so they are batched
+    def __init__(self, level=logging.WARNING, max_records=50):
+        super().__init__(level)
+        self.max_records = max_records
+        self.records = list()
+        self.dropped = 0
+
+        self.setFormatter(logging.Formatter("%(levelname)s %(name)s: %(message)s"))
+
+    def emit(self, record):
+        if len(self.records) >= self.max_records:
+            self.dropped += 1
+            return
+
+        self.records.append(self.format(record))
+
+    def take(self):
+        records, self.records = self.records, list()
+        if self.dropped:
+            records.append(f"... and {self.dropped} more")
+            self.dropped = 0
+
+        return "\n".join(records)
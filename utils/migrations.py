@@ -0,0 +1,96 @@
+import json
+from os.path import exists
+
+try:    from fcntl import LOCK_EX, LOCK_UN, flock
+except ImportError:    flock = None # Windows, where only one TTS Bot is expected to run from a folder
+
+# The files from before there were migrations, made on the first start with what the migrations would leave them as
+first_run_files = {"settings.json": dict(), "setlangs.json": dict(), "blocked_users.json": {"users": dict(), "guilds": dict()}}
+
+def create_settings_audit():
+    if not exists("settings_audit.json"):
+        with open("settings_audit.json", "w") as f:    json.dump(dict(), f)
+
+def create_analytics():
+    if not exists("analytics.json"):
+        with open("analytics.json", "w") as f:    json.dump(dict(), f)
+
+def create_saved_queue():
+    if not exists("saved_queue.json"):
+        with open("saved_queue.json", "w") as f:    json.dump(dict(), f)
+
+def create_voice_channels():
+    if not exists("voice_channels.json"):
+        with open("voice_channels.json", "w") as f:    json.dump(dict(), f)
+
+def create_opted_out():
+    if not exists("opted_out.json"):
+        with open("opted_out.json", "w") as f:    json.dump(list(), f)
+
+def block_guilds():
+    with open("blocked_users.json") as f:    blocked_users = json.load(f)
+
+    if isinstance(blocked_users, list):
+        blocked_users = {"users": {str(user_id): "" for user_id in blocked_users}, "guilds": dict()}
+        with open("blocked_users.json", "w") as f:    json.dump(blocked_users, f)
+
+def create_trials():
+    if not exists("trials.json"):
+        with open("trials.json", "w") as f:    json.dump(dict(), f)
+
+def create_votes():
+    if not exists("votes.json"):
+        with open("votes.json", "w") as f:    json.dump(dict(), f)
+
+def create_voices():
+    if not exists("voices.json"):
+        with open("voices.json", "w") as f:    json.dump(dict(), f)
+
+def multiple_prefixes():
+    with open("settings.json") as f:    settings = json.load(f)
+
+    for guild_settings in settings.values():
+        if "prefix" in guild_settings:
+            guild_settings["prefixes"] = [guild_settings.pop("prefix")]
+
+    with open("settings.json", "w") as f:    json.dump(settings, f)
+
+# The position of a migration is its version, so only ever add new migrations to the end
+migrations = (
+    create_settings_audit,
+    create_analytics,
+    create_saved_queue,
+    create_voice_channels,
+    create_opted_out,
+    block_guilds,
+    create_trials,
+    create_votes,
+    multiple_prefixes,
+    create_voices,
+)
+
+def create_first_run_files():
+    for file_name, empty in first_run_files.items():
+        if not exists(file_name):
+            print(f"Creating {file_name}")
+            with open(file_name, "w") as f:    json.dump(empty, f)
+
+def run_migrations():
+    # Locked so that several shards or processes started together don't run the same migration twice
+    with open("migrations.lock", "w") as lock:
+        if flock:   flock(lock, LOCK_EX)
+
+        create_first_run_files()
+
+        version = 0
+        if exists("migrations.json"):
+            with open("migrations.json") as f:  version = json.load(f)["version"]
+
+        for migration in migrations[version:]:
+            print(f"Running migration {version + 1}: {migration.__name__}")
+            migration()
+
+            version += 1
+            with open("migrations.json", "w") as f:    json.dump({"version": version}, f)
+
+        if flock:   flock(lock, LOCK_UN)
@@ -1,12 +1,30 @@
 import json
+from datetime import date, timedelta
+from time import time
 
 from utils.basic import get_value
+from utils.migrations import run_migrations
+
+run_migrations()
 
 with open("settings.json") as f:    settings = json.load(f)
 with open("setlangs.json") as f:    setlangs = json.load(f)
 with open("blocked_users.json") as f:    blocked_users = json.load(f)
+with open("settings_audit.json") as f:    settings_audit = json.load(f)
+with open("analytics.json") as f:    analytics = json.load(f)
+with open("saved_queue.json") as f:    saved_queue = json.load(f)
+with open("voice_channels.json") as f:    voice_channels = json.load(f)
+with open("opted_out.json") as f:    opted_out = json.load(f)
+with open("trials.json") as f:    trials = json.load(f)
+with open("votes.json") as f:    votes = json.load(f)
+with open("voices.json") as f:    voices = json.load(f)
+
+default_settings = {"channel": 0, "xsaid": True, "xsaid_on_change": False, "xsaid_format": "", "auto_join": False, "follow": 0, "bot_ignore": True, "announce_joins": False, "announce_events": "off", "now_playing": False, "required_role": 0, "pause_role": 0, "required_prefix": "", "allow_dm_tts": False, "read_attachments": False, "read_replies": False, "links": "domain", "code_blocks": "describe", "read_emoji": True, "read_reactions": False, "skip_spoilers": True, "msg_length": 1000, "repeated_chars": 0, "skip_repeated": False, "audio_max_time": 30, "volume": 100, "normalize": False, "trim_silence": True, "adaptive_speed": False, "bitrate": 0, "overflow": "drop_new", "voice_mode": "server", "elevenlabs_voice": "", "openai_voice": "", "premium": False, "nicknames": dict(), "voice_aliases": dict(), "role_voices": dict(), "channel_voices": dict(), "cooldowns": dict(), "language": "en", "prefixes": ["-"]}
 
-default_settings = {"channel": 0, "xsaid": True, "auto_join": False, "bot_ignore": True, "nicknames": dict()}
+class _Guild():
+    # Stand in for a discord.Guild when only the ID is stored
+    def __init__(self, guild_id):
+        self.id = guild_id
 
 class settings_class():
     def save():
@@ -31,21 +49,21 @@ class settings_class():
     def get(guild, setting):
         return get_value(settings, str(guild.id), setting, default_value=default_settings[setting])
 
+    def get_all(guild):
+        return {**default_settings, **get_value(settings, str(guild.id), default_value=dict())}
+
     def set(guild, setting, value):
         guild = str(guild.id)
 
-        if guild in settings:
-            if value == default_settings[setting] and setting in settings[guild]:
-                del settings[guild][setting]
-                return
+        if value == default_settings[setting]:
+            if guild in settings:
+                settings[guild].pop(setting, None)
 
-            if settings[guild] == dict():
-                del settings[guild]
-                return
-        else:
-            settings[guild] = dict()
+                if settings[guild] == dict():
+                    del settings[guild]
+            return
 
-        settings[guild][setting] = value
+        settings.setdefault(guild, dict())[setting] = value
 
     class nickname():
         def get(guild, user):
@@ -55,7 +73,7 @@ class settings_class():
             return nickname
 
         def set(guild, user, nickname):
-            nicknames = settings_class.get(guild, "nicknames")
+            nicknames = settings_class.get(guild, "nicknames").copy()
 
             user_id = str(user.id)
 
@@ -68,6 +86,13 @@ class settings_class():
 
             settings_class.set(guild, "nicknames", nicknames)
 
+        def export_user(user):
+            return {guild_id: guild_settings["nicknames"][str(user.id)] for guild_id, guild_settings in settings.items() if str(user.id) in guild_settings.get("nicknames", dict())}
+
+        def remove_user(user):
+            for guild_id in settings_class.nickname.export_user(user):
+                settings_class.nickname.set(_Guild(guild_id), user, "")
+
 class setlangs_class():
     def save():
         with open("setlangs.json", "w") as f:    json.dump(setlangs, f)
@@ -80,6 +105,12 @@ class setlangs_class():
     def get(user):
         return get_value(setlangs, str(user.id), default_value="en-us")
 
+    def export_user(user):
+        return setlangs.get(str(user.id))
+
+    def remove_user(user):
+        setlangs.pop(str(user.id), None)
+
     def set(user, value):
         user = str(user.id)
         value = value.lower()
@@ -90,14 +121,231 @@ class setlangs_class():
             setlangs[user] = value
 
 class blocked_users_class():
+    # Blocked servers are kept in here too, both are id: reason
     def save():
         with open("blocked_users.json", "w") as f:    json.dump(blocked_users, f)
 
     def check(user):
-        return user.id in blocked_users
+        return str(user.id) in blocked_users["users"]
+
+    def add(user, reason=""):
+        blocked_users["users"][str(user.id)] = reason
+
+    def remove(user):
+        blocked_users["users"].pop(str(user.id), None)
+
+    def check_guild(guild):
+        return str(guild.id) in blocked_users["guilds"]
+
+    def guild_reason(guild):
+        return blocked_users["guilds"].get(str(guild.id), "")
+
+    def add_guild(guild, reason=""):
+        blocked_users["guilds"][str(guild.id)] = reason
+
+    def remove_guild(guild):
+        blocked_users["guilds"].pop(str(guild.id), None)
+
+class settings_audit_class():
+    def save():
+        with open("settings_audit.json", "w") as f:    json.dump(settings_audit, f)
+
+    def record(guild, user, setting, old_value, new_value):
+        changes = settings_audit.setdefault(str(guild.id), list())
+        changes.append({"user": user.id, "setting": setting, "old": old_value, "new": new_value, "time": int(time())})
+        del changes[:-50]
+
+    def get(guild, amount=20):
+        return get_value(settings_audit, str(guild.id), default_value=list())[-amount:]
+
+    def remove(guild):
+        settings_audit.pop(str(guild.id), None)
+
+    def export_user(user):
+        return {guild_id: [change for change in changes if change["user"] == user.id] for guild_id, changes in settings_audit.items() if any(change["user"] == user.id for change in changes)}
+
+    def remove_user(user):
+        for changes in settings_audit.values():
+            changes[:] = [change for change in changes if change["user"] != user.id]
+
+class analytics_class():
+    # Counters are only kept in memory and written with the other files, instead of on every message
+    def save():
+        with open("analytics.json", "w") as f:    json.dump(analytics, f)
+
+    def add(counter, amount=1):
+        analytics[counter] = analytics.get(counter, 0) + amount
+
+    def get(counter):
+        return analytics.get(counter, 0)
+
+    def add_guild(guild, user, characters):
+        today = date.today().isoformat()
+        days = analytics.setdefault("guilds", dict()).setdefault(str(guild.id), dict())
+        day = days.setdefault(today, {"messages": 0, "characters": 0, "speakers": list()})
+
+        day["messages"] += 1
+        day["characters"] += characters
+        if user.id not in day["speakers"]:  day["speakers"].append(user.id)
+
+        # Only the last 30 days are kept
+        oldest = (date.today() - timedelta(days=30)).isoformat()
+        for old_day in [day for day in days if day < oldest]:
+            del days[old_day]
+
+    def get_guild(guild, amount=7):
+        days = get_value(analytics, "guilds", str(guild.id), default_value=dict())
+        empty = {"messages": 0, "characters": 0, "speakers": list()}
+
+        return [
+            ((date.today() - timedelta(days=days_ago)).isoformat(), days.get((date.today() - timedelta(days=days_ago)).isoformat(), empty))
+            for days_ago in reversed(range(amount))
+        ]
+
+    def remove(guild):
+        analytics.get("guilds", dict()).pop(str(guild.id), None)
+
+    def export_user(user):
+        return {guild_id: [day_name for day_name, day in days.items() if user.id in day["speakers"]] for guild_id, days in analytics.get("guilds", dict()).items() if any(user.id in day["speakers"] for day in days.values())}
+
+    def remove_user(user):
+        for days in analytics.get("guilds", dict()).values():
+            for day in days.values():
+                if user.id in day["speakers"]:  day["speakers"].remove(user.id)
+
+class saved_queue_class():
+    # The text of queued messages, so they can still be read after a restart
+    def save():
+        with open("saved_queue.json", "w") as f:    json.dump(saved_queue, f)
+
+    def add(guild, message, text, lang):
+        saved_queue.setdefault(str(guild.id), dict())[str(message.id)] = {"channel": message.channel.id, "author": message.author.id, "text": text, "lang": lang, "timestamp": time()}
+
+    def merge(guild, message_id, text):
+        entry = get_value(saved_queue, str(guild.id), str(message_id))
+        if entry:   entry["text"] += f". {text}"
+
+    def get(guild):
+        return sorted((int(message_id), entry) for message_id, entry in saved_queue.get(str(guild.id), dict()).items())
+
+    def remove(guild, message_id):
+        saved_queue.get(str(guild.id), dict()).pop(str(message_id), None)
+        if saved_queue.get(str(guild.id)) == dict():    del saved_queue[str(guild.id)]
+
+    def clear(guild):
+        saved_queue.pop(str(guild.id), None)
+
+    def export_user(user):
+        return [entry for entries in saved_queue.values() for entry in entries.values() if entry["author"] == user.id]
+
+    def remove_user(user):
+        for entries in saved_queue.values():
+            for message_id, entry in list(entries.items()):
+                if entry["author"] == user.id:  del entries[message_id]
+
+    def expire(max_age):
+        for guild_id, entries in list(saved_queue.items()):
+            for message_id, entry in list(entries.items()):
+                if time() - entry["timestamp"] > max_age:   del entries[message_id]
+
+            if entries == dict():   del saved_queue[guild_id]
+
+class voice_channels_class():
+    # The voice channel TTS Bot is in for each guild, so it can rejoin them after a restart
+    def save():
+        with open("voice_channels.json", "w") as f:    json.dump(voice_channels, f)
+
+    def set(guild, channel):
+        voice_channels[str(guild.id)] = channel.id
+
+    def remove(guild):
+        voice_channels.pop(str(guild.id), None)
+
+    def get_all():
+        return {int(guild_id): channel_id for guild_id, channel_id in voice_channels.items()}
+
+class opted_out_class():
+    # People who never want their messages read, checked before anything else is done with a message
+    def save():
+        with open("opted_out.json", "w") as f:    json.dump(opted_out, f)
+
+    def check(user):
+        return user.id in opted_out
 
     def add(user):
-        blocked_users.append(user.id)
+        opted_out.append(user.id)
 
     def remove(user):
-        blocked_users.remove(user.id)
+        opted_out.remove(user.id)
+
+class trials_class():
+    # Every guild can only have one trial, so they are kept after they expire
+    def save():
+        with open("trials.json", "w") as f:    json.dump(trials, f)
+
+    def used(guild):
+        return str(guild.id) in trials
+
+    def active(guild):
+        return get_value(trials, str(guild.id), "expires", default_value=0) > time()
+
+    def start(guild, seconds=7 * 86400):
+        trials[str(guild.id)] = {"expires": time() + seconds, "notified": False}
+        return trials[str(guild.id)]["expires"]
+
+    def expired():
+        # Expired trials that haven't had a reminder sent yet, marked as notified once returned
+        guild_ids = [int(guild_id) for guild_id, trial in trials.items() if trial["expires"] <= time() and not trial["notified"]]
+        for guild_id in guild_ids:  trials[str(guild_id)]["notified"] = True
+
+        return guild_ids
+
+    def remove(guild):
+        trials.pop(str(guild.id), None)
+
+class votes_class():
+    # When each voter's perks run out, expired votes are removed on save
+    def save():
+        for user_id, expires in list(votes.items()):
+            if expires <= time():   del votes[user_id]
+
+        with open("votes.json", "w") as f:    json.dump(votes, f)
+
+    def add(user, hours=24):
+        votes[str(user.id)] = time() + hours * 3600
+
+    def remaining(user):
+        return max(0, votes.get(str(user.id), 0) - time())
+
+    def active(user):
+        return votes_class.remaining(user) > 0
+
+    def export_user(user):
+        return votes.get(str(user.id))
+
+    def remove_user(user):
+        votes.pop(str(user.id), None)
+
+class voices_class():
+    # The last voice list fetched from each backend, so voices can be checked and listed while its API is down
+    def save():
+        with open("voices.json", "w") as f:    json.dump(voices, f)
+
+    def get(backend):
+        return get_value(voices, backend, "voices", default_value=dict())
+
+    def set(backend, backend_voices):
+        voices[backend] = {"voices": backend_voices, "updated": time()}
+
+    def updated(backend):
+        return get_value(voices, backend, "updated", default_value=0)
+
+# Everything that stores user IDs, for -mydata export and -mydata delete, new stores need adding here
+user_data = {
+    "voice": setlangs_class,
+    "nicknames": settings_class.nickname,
+    "settings_changes": settings_audit_class,
+    "usage": analytics_class,
+    "queued_messages": saved_queue_class,
+    "votes": votes_class,
+}
@@ -0,0 +1,99 @@
+import json
+from os import listdir
+from os.path import splitext
+from re import IGNORECASE, compile, escape
+
+# How to read numbers, times and abbreviations out loud, one file per language in verbalize/, named by the start of the voice code
+rules = dict()
+for file_name in listdir("verbalize"):
+    lang, ext = splitext(file_name)
+    if ext != ".json":  continue
+
+    with open(f"verbalize/{file_name}", encoding="utf-8") as f:  rules[lang] = json.load(f)
+
+time_regex = compile(r"(?<![\d:])([01]?\d|2[0-3]):([0-5]\d)(?![\d:])")
+regexes = {
+    lang: {
+        "abbreviations": compile(r"\b(%s)\b" % "|".join(map(escape, lang_rules["abbreviations"])), IGNORECASE),
+        "ordinals": compile(r"\b(\d{1,15})(?:%s)\b" % "|".join(map(escape, lang_rules["ordinal_suffixes"]))),
+        "codes": compile(r"\b(%s)(\s+)(\d{1,15})(?![\w]|[.,-]\d)" % "|".join(map(escape, lang_rules["code_words"])), IGNORECASE),
+        # Digits next to a dash or another dotted group are phone numbers or versions, so they are left for the engine
+        "numbers": compile(r"(?<![\w.,])(?<!\d-)(\d{1,3}(?:%s\d{3})+|\d{1,15})(?:%s(\d+))?(?![\w]|[.,-]\d)" % (escape(lang_rules["thousands_separator"]), escape(lang_rules["decimal_separator"]))),
+    }
+    for lang, lang_rules in rules.items()
+}
+
+def number_to_words(number, lang_rules):
+    ones, tens = lang_rules["ones"], lang_rules["tens"]
+    if number < len(ones):
+        return ones[number]
+    if number < 100:
+        return tens[number // 10] if not number % 10 else lang_rules["tens_and_ones"].format(tens=tens[number // 10], ones=ones[number % 10])
+    if number < 1000:
+        hundreds = lang_rules["hundreds"].format(ones=ones[number // 100])
+        return hundreds if not number % 100 else f"{hundreds} {number_to_words(number % 100, lang_rules)}"
+
+    scales = lang_rules["scales"]
+    if number >= 1000 ** len(scales):
+        return str(number)
+
+    # Each group of three digits is read on its own, followed by its scale
+    groups = [number // 1000 ** power % 1000 for power in range(len(scales))]
+    return " ".join(f"{number_to_words(group, lang_rules)} {scale}".strip() for group, scale in reversed(list(zip(groups, scales))) if group)
+
+def digits_to_words(digits, lang_rules):
+    return " ".join(lang_rules["ones"][int(digit)] for digit in digits)
+
+def read_number(whole, decimals, lang_rules):
+    # Numbers starting with 0, such as 007, are codes rather than amounts
+    if len(whole) > 1 and whole.startswith("0") and whole.isdigit():
+        return digits_to_words(whole, lang_rules)
+
+    words = number_to_words(int(whole.replace(lang_rules["thousands_separator"], "")), lang_rules)
+    if decimals:
+        words = f"{words} {lang_rules['decimal_point']} {digits_to_words(decimals, lang_rules)}"
+
+    return words
+
+def ordinal_to_words(number, lang_rules):
+    # Only the last word changes, such as twenty-one to twenty-first
+    words = number_to_words(number, lang_rules)
+    split_at = max(words.rfind(" "), words.rfind("-")) + 1
+    start, last = words[:split_at], words[split_at:]
+
+    if last in lang_rules["ordinals"]:
+        return start + lang_rules["ordinals"][last]
+    for ending, replacement in lang_rules["ordinal_endings"].items():
+        if last.endswith(ending):
+            return start + last[:-len(ending)] + replacement
+
+    return start + last + lang_rules["ordinal_suffix"]
+
+def time_to_words(hour, minute, lang_rules):
+    # 24 hour times are read in 12 hours with am or pm, times such as 2:30 could be either so are read as they are
+    time_rules = lang_rules["time"]
+    period = time_rules["periods"][1] if hour > 12 else time_rules["periods"][0] if hour == 0 else ""
+    hour_words = number_to_words(hour % 12 or 12, lang_rules)
+
+    if minute == 0:
+        template = time_rules["on_the_hour"]
+    elif minute < 10:
+        template = time_rules["minutes_under_ten"]
+    else:
+        template = time_rules["minutes"]
+
+    return template.format(hour=hour_words, minutes=number_to_words(minute, lang_rules), period=period).strip()
+
+def verbalize(text, lang):
+    # Languages without a file are left alone, gTTS reads their numbers well enough
+    lang = lang.split("-")[0]
+    if lang not in rules:
+        return text
+
+    lang_rules, lang_regexes = rules[lang], regexes[lang]
+    text = lang_regexes["abbreviations"].sub(lambda match: lang_rules["abbreviations"][match.group(1).lower()], text)
+    text = time_regex.sub(lambda match: time_to_words(int(match.group(1)), int(match.group(2)), lang_rules), text)
+    # Numbers after words such as "call" or "room" are read digit by digit
+    text = lang_regexes["codes"].sub(lambda match: f"{match.group(1)}{match.group(2)}{digits_to_words(match.group(3), lang_rules)}", text)
+    text = lang_regexes["ordinals"].sub(lambda match: ordinal_to_words(int(match.group(1)), lang_rules), text)
+    return lang_regexes["numbers"].sub(lambda match: read_number(match.group(1), match.group(2), lang_rules), text)